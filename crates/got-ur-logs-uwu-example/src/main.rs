@@ -19,7 +19,7 @@ use got_ur_logs_uwu::{
 };
 
 fn main() {
-    Logger::<Severity, Message<Severity>>::global()
+    Logger::<Severity, Message<Severity>>::global_mut()
         .add_writer(ConsoleWriter::new_stdout(Plaintext::new_default()));
 
     log_message!(severity = Severity::Info, text = "hello, world");
@@ -0,0 +1,206 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+use tokio::{
+    sync::{mpsc, oneshot},
+    task::{self, JoinHandle},
+};
+
+use crate::{Error, HasSeverity, HasText, IsSeverity, Logger, Result};
+
+/// How many messages can be queued before [`BackgroundLogger::log_message`] waits for the
+/// background task to catch up.
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// A command sent from a [`BackgroundLogger`] to its background task.
+enum BackgroundCommand<Message> {
+    /// Log a message.
+    Log(Message),
+    /// Drain the logger and notify the sender once all of the commands before this one have been
+    /// processed.
+    Flush(oneshot::Sender<()>),
+}
+
+/// A handle to a logger that has been moved into a background task.
+///
+/// Writers are called from a blocking task so that slow writers do not stall the async executor.
+/// It is created with [`Logger::spawn_background_logger`] and requires the `tokio` feature.
+///
+/// # Example
+///
+/// ```
+/// # use got_ur_logs_uwu::{
+/// #     writers::ConsoleWriter, Logger, Message, Severity, formatters::Plaintext, FromCoreFields
+/// # };
+/// #
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> got_ur_logs_uwu::Result<()> {
+/// let mut logger = Logger::<Severity, Message<Severity>>::default();
+///
+/// logger.add_writer(
+///     ConsoleWriter::new_stdout(
+///         Plaintext::new_default()
+///     )
+/// );
+///
+/// let background_logger = logger.spawn_background_logger();
+///
+/// background_logger
+///     .log_message(Message::from_core_fields(Severity::Info, "hello, world"))
+///     .await?;
+///
+/// // Get the logger back once all the queued messages have been written
+/// let logger = background_logger.shutdown().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct BackgroundLogger<Severity: IsSeverity, Message: HasSeverity<Severity> + HasText> {
+    sender: mpsc::Sender<BackgroundCommand<Message>>,
+    task: JoinHandle<Logger<Severity, Message>>,
+}
+
+impl<Severity: 'static + IsSeverity, Message: 'static + HasSeverity<Severity> + HasText + Send>
+    BackgroundLogger<Severity, Message>
+{
+    /// Moves a logger into a background task.
+    pub(crate) fn spawn(logger: Logger<Severity, Message>) -> Self {
+        let (sender, mut receiver) = mpsc::channel(CHANNEL_CAPACITY);
+
+        let task = task::spawn_blocking(move || {
+            while let Some(command) = receiver.blocking_recv() {
+                match command {
+                    BackgroundCommand::Log(message) => logger.log_message(message),
+                    BackgroundCommand::Flush(notify) => {
+                        logger.drain();
+
+                        // The flush may have been cancelled, in which case nobody is waiting
+                        let _ = notify.send(());
+                    }
+                }
+            }
+
+            logger
+        });
+
+        Self { sender, task }
+    }
+
+    /// Queues a message to be logged by the background task.
+    ///
+    /// This waits only if the queue is full.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message object that will be passed along to the writers
+    ///
+    /// # Returns
+    ///
+    /// Returns [`Error::BackgroundLoggerStopped`] if the background task is no longer running.
+    pub async fn log_message(&self, message: Message) -> Result<()> {
+        self.sender
+            .send(BackgroundCommand::Log(message))
+            .await
+            .map_err(|_| Error::BackgroundLoggerStopped)
+    }
+
+    /// Waits until all of the messages queued so far have been logged and the writers have been
+    /// drained with [`Logger::drain`].
+    ///
+    /// # Returns
+    ///
+    /// Returns [`Error::BackgroundLoggerStopped`] if the background task is no longer running.
+    pub async fn flush(&self) -> Result<()> {
+        let (notify, notified) = oneshot::channel();
+
+        self.sender
+            .send(BackgroundCommand::Flush(notify))
+            .await
+            .map_err(|_| Error::BackgroundLoggerStopped)?;
+
+        notified.await.map_err(|_| Error::BackgroundLoggerStopped)
+    }
+
+    /// Stops the background task once all queued messages have been logged.
+    ///
+    /// # Returns
+    ///
+    /// Returns the logger so that it can be used synchronously again, or
+    /// [`Error::BackgroundLoggerStopped`] if the background task panicked.
+    pub async fn shutdown(self) -> Result<Logger<Severity, Message>> {
+        drop(self.sender);
+
+        self.task.await.map_err(|_| Error::BackgroundLoggerStopped)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::{writers::CaptureWriter, FromCoreFields, Message, Severity};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn logs_messages() -> Result<()> {
+        let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+        logger.add_writer_shared(writer.clone());
+
+        let background_logger = logger.spawn_background_logger();
+
+        background_logger
+            .log_message(Message::from_core_fields(Severity::Info, "first"))
+            .await?;
+        background_logger
+            .log_message(Message::from_core_fields(Severity::Info, "second"))
+            .await?;
+
+        background_logger.flush().await?;
+
+        assert_eq!(writer.lock().unwrap().message_count(), 2);
+
+        let logger = background_logger.shutdown().await?;
+
+        assert_eq!(logger.snapshot().writer_count, 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn shutdown_drains_queue() -> Result<()> {
+        let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+        logger.add_writer_shared(writer.clone());
+
+        let background_logger = logger.spawn_background_logger();
+
+        for _ in 0..10 {
+            background_logger
+                .log_message(Message::from_core_fields(Severity::Info, "test"))
+                .await?;
+        }
+
+        background_logger.shutdown().await?;
+
+        assert_eq!(writer.lock().unwrap().message_count(), 10);
+
+        Ok(())
+    }
+}
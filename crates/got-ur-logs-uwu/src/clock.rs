@@ -0,0 +1,50 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+use std::time::SystemTime;
+
+/// A trait implemented by sources of the current time.
+///
+/// Loggers use a clock to get the current time so that it can be replaced in tests where the
+/// time needs to be deterministic.
+///
+/// # Example
+///
+/// ```
+/// # use std::time::{Duration, SystemTime};
+/// # use got_ur_logs_uwu::Clock;
+/// #
+/// struct FakeClock;
+///
+/// impl Clock for FakeClock {
+///     fn now(&self) -> SystemTime {
+///         SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000)
+///     }
+/// }
+/// ```
+pub trait Clock: Send + Sync {
+    /// Gets the current time.
+    fn now(&self) -> SystemTime;
+}
+
+/// The default clock, which uses the system time.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
@@ -13,20 +13,36 @@
 // You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
 // not, see <https://www.gnu.org/licenses/>.
 
-use std::{io, result};
+use std::{fmt, io, result};
 use thiserror::Error;
 
 /// Crate error type
 #[derive(Debug, Error)]
 pub enum Error {
-    #[error("IO error: {0}")]
+    #[error("Failed to write log message: {0}")]
     IOError(io::Error),
-    #[error("Handlebars render error: {0}")]
+    #[error("Failed to render log template: {0}")]
     HandlebarsRenderError(Box<handlebars::RenderError>),
-    #[error("Handlebars template error: {0}")]
+    #[error("Log writer failed: {0}")]
+    WriterError(String),
+    #[error("Failed to format log message: {0}")]
+    FmtError(fmt::Error),
+    #[error("Failed to serialize log message as JSON: {0}")]
+    JsonError(serde_json::Error),
+    #[error("Background logger has stopped")]
+    BackgroundLoggerStopped,
+    #[error("Invalid log template{}: {}", template_error_position(.0), .0.reason())]
     HandlebarsTemplateError(Box<handlebars::TemplateError>),
 }
 
+/// Describes where in the template a template error occurred, if known.
+fn template_error_position(error: &handlebars::TemplateError) -> String {
+    match (error.line_no, error.column_no) {
+        (Some(line), Some(column)) => format!(" at line {}, column {}", line, column),
+        _ => String::new(),
+    }
+}
+
 impl From<io::Error> for Error {
     fn from(error: io::Error) -> Self {
         Self::IOError(error)
@@ -45,5 +61,93 @@ impl From<handlebars::TemplateError> for Error {
     }
 }
 
+impl From<fmt::Error> for Error {
+    fn from(error: fmt::Error) -> Self {
+        Self::FmtError(error)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        // Failing to write the JSON out is an I/O error, not a serialization error
+        if error.io_error_kind().is_some() {
+            Self::IOError(error.into())
+        } else {
+            Self::JsonError(error)
+        }
+    }
+}
+
 /// Crate result type
 pub type Result<Value> = result::Result<Value, Error>;
+
+#[cfg(test)]
+mod tests {
+    use crate::formatters::Plaintext;
+
+    use super::*;
+
+    #[test]
+    fn io_error_display() {
+        let error = Error::from(io::Error::new(io::ErrorKind::BrokenPipe, "broken pipe"));
+
+        assert_eq!(
+            error.to_string(),
+            "Failed to write log message: broken pipe"
+        );
+    }
+
+    #[test]
+    fn fmt_error_display() {
+        let error = Error::from(fmt::Error);
+
+        assert_eq!(
+            error.to_string(),
+            "Failed to format log message: an error occurred when formatting an argument"
+        );
+    }
+
+    #[test]
+    fn json_error_display() {
+        let error = Error::from(serde_json::from_str::<serde_json::Value>("{").unwrap_err());
+
+        assert_eq!(
+            error.to_string(),
+            "Failed to serialize log message as JSON: EOF while parsing an object at line 1 column 1"
+        );
+    }
+
+    #[test]
+    fn json_io_error() {
+        struct BrokenPipe;
+
+        impl io::Write for BrokenPipe {
+            fn write(&mut self, _buffer: &[u8]) -> io::Result<usize> {
+                Err(io::Error::new(io::ErrorKind::BrokenPipe, "broken pipe"))
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let error = Error::from(serde_json::to_writer(BrokenPipe, "hello").unwrap_err());
+
+        match error {
+            Error::IOError(error) => assert_eq!(error.kind(), io::ErrorKind::BrokenPipe),
+            error => panic!("expected an I/O error, got {:?}", error),
+        }
+    }
+
+    #[test]
+    fn template_error_display() {
+        let error = match Plaintext::new("[{{severity}] {{text}}") {
+            Ok(_) => panic!("template should be invalid"),
+            Err(error) => error,
+        };
+
+        assert!(error
+            .to_string()
+            .starts_with("Invalid log template at line 1, column "));
+    }
+}
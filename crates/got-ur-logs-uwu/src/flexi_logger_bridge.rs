@@ -0,0 +1,126 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+use std::{io, sync::Arc};
+
+use flexi_logger::{writers::LogWriter, DeferredNow};
+use log::Record;
+
+use crate::{FromCoreFields, HasFields, HasSeverity, HasText, Logger, Severity};
+
+/// A [`flexi_logger`] writer that forwards records to a logger.
+///
+/// This lets applications that are set up with `flexi_logger` migrate to `got-ur-logs-uwu`
+/// gradually, by adding this as one of `flexi_logger`'s writers. Record levels are converted with
+/// [`Severity::from_log_level`], the formatted arguments become the text of the message, and the
+/// target of the record is added as the `target` field.
+///
+/// Requires the `flexi-logger` feature.
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::sync::Arc;
+/// # use got_ur_logs_uwu::{FlexiLogWriter, Logger, Message, Severity};
+/// #
+/// let logger = Arc::new(Logger::<Severity, Message<Severity>>::default());
+///
+/// flexi_logger::Logger::try_with_str("info")
+///     .expect("invalid log specification")
+///     .log_to_writer(Box::new(FlexiLogWriter::new(logger)))
+///     .start()
+///     .expect("unable to start flexi_logger");
+///
+/// log::info!("hello, world"); // ← This is logged by the got-ur-logs-uwu logger
+/// ```
+pub struct FlexiLogWriter<Message: HasSeverity<Severity> + HasText> {
+    logger: Arc<Logger<Severity, Message>>,
+}
+
+impl<Message: HasSeverity<Severity> + HasText> FlexiLogWriter<Message> {
+    /// Create a new writer that forwards to a logger.
+    ///
+    /// # Arguments
+    ///
+    /// * `logger` - The logger to forward records to
+    pub fn new(logger: Arc<Logger<Severity, Message>>) -> Self {
+        Self { logger }
+    }
+}
+
+impl<Message: HasSeverity<Severity> + HasText + FromCoreFields<Severity> + HasFields> LogWriter
+    for FlexiLogWriter<Message>
+{
+    fn write(&self, _now: &mut DeferredNow, record: &Record) -> io::Result<()> {
+        let severity = Severity::from_log_level(record.level());
+
+        if self.logger.is_enabled(&severity) {
+            self.logger.log_with_fields(
+                severity,
+                &record.args().to_string(),
+                vec![("target".to_owned(), record.target().to_owned())],
+            );
+        }
+
+        Ok(())
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        self.logger.drain();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use log::Level;
+
+    use crate::{writers::CaptureWriter, Message};
+
+    use super::*;
+
+    #[test]
+    fn forwards_records() {
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+        let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+        logger.add_writer_shared(writer.clone());
+
+        let flexi_log_writer = FlexiLogWriter::new(Arc::new(logger));
+
+        flexi_log_writer
+            .write(
+                &mut DeferredNow::new(),
+                &Record::builder()
+                    .level(Level::Warn)
+                    .target("app")
+                    .args(format_args!("disk {}% full", 90))
+                    .build(),
+            )
+            .unwrap();
+
+        flexi_log_writer.flush().unwrap();
+
+        let message = writer.lock().unwrap().last_message().unwrap();
+
+        assert_eq!(*message.severity(), Severity::Warning);
+        assert_eq!(message.text(), "disk 90% full");
+        assert_eq!(message.fields(), [("target".to_owned(), "app".to_owned())]);
+    }
+}
@@ -13,6 +13,61 @@
 // You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
 // not, see <https://www.gnu.org/licenses/>.
 
+mod compact;
+mod gelf;
+mod json;
+mod logfmt;
+mod logstash;
 mod plaintext;
 
-pub use plaintext::Plaintext;
+pub use compact::Compact;
+pub use gelf::Gelf;
+pub use json::{Json, JsonFieldNames};
+pub use logfmt::Logfmt;
+pub use logstash::Logstash;
+pub use plaintext::{CompiledTemplate, Plaintext};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Format, FromCoreFields, HasFields, Message, Severity};
+
+    /// Asserts that a formatter writes the same output through [`Format::format_generic`] as
+    /// through [`Format::format`].
+    fn assert_format_generic_matches_format<FormatterType: Format<Severity, Message<Severity>>>(
+        mut formatter: FormatterType,
+    ) {
+        let mut message = Message::from_core_fields(Severity::Warning, "user \"bob\" logged in");
+
+        message
+            .fields_mut()
+            .push(("user_id".to_owned(), "42".to_owned()));
+
+        let mut dynamic = Vec::new();
+        let mut generic = Vec::new();
+
+        formatter
+            .format(&message, &mut dynamic)
+            .expect("formatting should succeed");
+        formatter
+            .format_generic(&message, &mut generic)
+            .expect("formatting should succeed");
+
+        assert_eq!(
+            String::from_utf8(generic).unwrap(),
+            String::from_utf8(dynamic).unwrap(),
+            "{} formatter",
+            formatter.name()
+        );
+    }
+
+    #[test]
+    fn format_generic_matches_format() {
+        assert_format_generic_matches_format(Compact::new());
+        assert_format_generic_matches_format(Gelf::new());
+        assert_format_generic_matches_format(Json::new());
+        assert_format_generic_matches_format(Logfmt::new());
+        assert_format_generic_matches_format(Logstash::new());
+        assert_format_generic_matches_format(Plaintext::new_default());
+    }
+}
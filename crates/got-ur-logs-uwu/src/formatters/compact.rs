@@ -0,0 +1,159 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+use std::{fmt, io};
+
+use crate::{traits::Format, HasSeverity, HasText, IsSeverity, Result};
+
+/// A formatter that outputs minimal messages for terminal applications.
+///
+/// Messages look like:
+///
+/// ```plaintext
+/// I: hello, world
+/// ```
+///
+/// The severity is abbreviated to the first character of its name, uppercased. Note that this
+/// makes [`Severity::Debug`](crate::Severity::Debug) and
+/// [`Severity::DeveloperWarning`](crate::Severity::DeveloperWarning) look the same.
+///
+/// # Example
+///
+/// ```
+/// # use got_ur_logs_uwu::formatters::Compact;
+/// #
+/// let formatter = Compact::new();
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Compact;
+
+impl Compact {
+    /// Creates a new compact formatter.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Formats a message to any kind of writer, so that it can be shared by [`Format::format`] and
+    /// [`Format::format_generic`].
+    fn format_to<
+        SeverityType: IsSeverity,
+        MessageType: HasSeverity<SeverityType> + HasText,
+        WriterType: io::Write + ?Sized,
+    >(
+        message: &MessageType,
+        writer: &mut WriterType,
+    ) -> Result<()> {
+        for character in abbreviate(message.severity())
+            .into_iter()
+            .flat_map(char::to_uppercase)
+        {
+            write!(writer, "{}", character)?;
+        }
+
+        write!(writer, ": {}", message.text())?;
+
+        Ok(())
+    }
+}
+
+/// Gets the first character of a severity's name without allocating.
+fn abbreviate<SeverityType: IsSeverity>(severity: &SeverityType) -> Option<char> {
+    /// Keeps the first character that is written and ignores the rest.
+    struct FirstCharacter(Option<char>);
+
+    impl fmt::Write for FirstCharacter {
+        fn write_str(&mut self, text: &str) -> fmt::Result {
+            if self.0.is_none() {
+                self.0 = text.chars().next();
+            }
+
+            Ok(())
+        }
+    }
+
+    let mut first_character = FirstCharacter(None);
+
+    // Writing to `FirstCharacter` never fails
+    let _ = fmt::write(&mut first_character, format_args!("{}", severity));
+
+    first_character.0
+}
+
+impl<SeverityType: IsSeverity, MessageType: HasSeverity<SeverityType> + HasText>
+    Format<SeverityType, MessageType> for Compact
+{
+    fn format(&mut self, message: &MessageType, writer: &mut dyn io::Write) -> Result<()> {
+        Self::format_to(message, writer)
+    }
+
+    fn format_generic<WriterType: io::Write>(
+        &mut self,
+        message: &MessageType,
+        writer: &mut WriterType,
+    ) -> Result<()> {
+        Self::format_to(message, writer)
+    }
+
+    fn name(&self) -> &'static str {
+        "compact"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FromCoreFields, Message, Severity};
+
+    fn format_to_string(message: &Message<Severity>) -> String {
+        let mut buffer = Vec::new();
+
+        Compact::new()
+            .format(message, &mut buffer)
+            .expect("formatting should succeed");
+
+        String::from_utf8(buffer).expect("output should be valid UTF-8")
+    }
+
+    #[test]
+    fn core_fields() {
+        assert_eq!(
+            format_to_string(&Message::from_core_fields(Severity::Info, "hello, world")),
+            "I: hello, world"
+        );
+        assert_eq!(
+            format_to_string(&Message::from_core_fields(Severity::Fatal, "goodbye")),
+            "F: goodbye"
+        );
+    }
+
+    #[test]
+    fn custom_severity() {
+        assert_eq!(
+            format_to_string(&Message::from_core_fields(
+                Severity::Custom(140, "audit"),
+                "user logged in"
+            )),
+            "A: user logged in"
+        );
+    }
+
+    #[test]
+    fn name() {
+        assert_eq!(
+            Format::<Severity, Message<Severity>>::name(&Compact::new()),
+            "compact"
+        );
+    }
+}
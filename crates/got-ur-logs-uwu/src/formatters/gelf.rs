@@ -0,0 +1,303 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+use std::{any::Any, io, time::UNIX_EPOCH};
+
+use gethostname::gethostname;
+use serde_json::{Map, Value};
+
+use crate::{
+    traits::Format, Error, HasFields, HasSeverity, HasText, HasTimestamp, IsSeverity, Result,
+    Severity,
+};
+
+/// The magic bytes that start every chunk of a chunked GELF message.
+const CHUNK_MAGIC: [u8; 2] = [0x1e, 0x0f];
+
+/// The size of the header at the start of every chunk of a chunked GELF message.
+const CHUNK_HEADER_SIZE: usize = 12;
+
+/// The maximum number of chunks a GELF message can be split into.
+const MAX_CHUNK_COUNT: usize = 128;
+
+/// The syslog level used for severities that do not have a mapping.
+const DEFAULT_LEVEL: u8 = 6;
+
+/// Gets the syslog level of a severity, if it is the built-in [`Severity`] type.
+///
+/// Custom severities use the level of the closest built-in severity below them.
+fn severity_level(severity: &dyn Any) -> Option<u8> {
+    severity
+        .downcast_ref::<Severity>()
+        .map(|severity| match severity {
+            Severity::Trace | Severity::Debug => 7,
+            Severity::Info => 6,
+            Severity::DeveloperWarning | Severity::Warning => 4,
+            Severity::Error => 3,
+            Severity::Fatal => 2,
+            Severity::Custom(..) => severity_level(
+                &Severity::from_index(severity.as_index())
+                    .expect("index of a severity should be valid"),
+            )
+            .expect("built-in severities should have a level"),
+        })
+}
+
+/// A formatter that outputs messages in the Graylog Extended Log Format (GELF) version 1.1.
+///
+/// Messages look like:
+///
+/// ```json
+/// {"_user_id":"42","host":"server-1","level":6,"short_message":"hello, world","timestamp":1696075200.25,"version":"1.1"}
+/// ```
+///
+/// The built-in [`Severity`] type is mapped to syslog levels, and other severity types are written
+/// as level 6 (informational). Structured fields are written as additional fields, prefixed with
+/// `_`. The `id` field is skipped since GELF reserves `_id`.
+///
+/// The output is a single uncompressed JSON object, which is suitable for GELF over TCP or HTTP.
+/// For UDP, payloads larger than a datagram can be split with [`Gelf::chunk`].
+///
+/// # Example
+///
+/// ```
+/// # use got_ur_logs_uwu::formatters::Gelf;
+/// #
+/// let formatter = Gelf::new();
+/// ```
+pub struct Gelf {
+    hostname: String,
+}
+
+impl Gelf {
+    /// Creates a new GELF formatter.
+    ///
+    /// GELF requires a `host` key on every message, which is filled in with the current machine's
+    /// hostname.
+    pub fn new() -> Self {
+        Self {
+            hostname: gethostname().to_string_lossy().into_owned(),
+        }
+    }
+
+    /// Splits a GELF payload into chunks for sending over UDP.
+    ///
+    /// Payloads that already fit are returned as a single unchunked datagram. Otherwise each
+    /// chunk starts with the GELF chunk header, made up of the magic bytes, the message ID, the
+    /// sequence number, and the sequence count.
+    ///
+    /// # Arguments
+    ///
+    /// * `payload` - The formatted, and optionally compressed, GELF message
+    /// * `message_id` - An ID that is unique to this message, used to reassemble the chunks
+    /// * `max_datagram_size` - The maximum size of each datagram, including the chunk header
+    ///
+    /// # Returns
+    ///
+    /// Returns the datagrams to send, or an error if the payload needs more than 128 chunks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::formatters::Gelf;
+    /// #
+    /// let datagrams = Gelf::chunk(&[0; 3000], 42, 1420).expect("payload is small enough");
+    ///
+    /// assert_eq!(datagrams.len(), 3);
+    /// ```
+    pub fn chunk(
+        payload: &[u8],
+        message_id: u64,
+        max_datagram_size: usize,
+    ) -> Result<Vec<Vec<u8>>> {
+        if payload.len() <= max_datagram_size {
+            return Ok(vec![payload.to_vec()]);
+        }
+
+        if max_datagram_size <= CHUNK_HEADER_SIZE {
+            return Err(Error::WriterError(format!(
+                "GELF datagram size of {} bytes is too small for the chunk header",
+                max_datagram_size
+            )));
+        }
+
+        let chunks = payload
+            .chunks(max_datagram_size - CHUNK_HEADER_SIZE)
+            .collect::<Vec<_>>();
+
+        if chunks.len() > MAX_CHUNK_COUNT {
+            return Err(Error::WriterError(format!(
+                "GELF message needs {} chunks, which is more than the maximum of {}",
+                chunks.len(),
+                MAX_CHUNK_COUNT
+            )));
+        }
+
+        Ok(chunks
+            .iter()
+            .enumerate()
+            .map(|(sequence_number, chunk)| {
+                let mut datagram = Vec::with_capacity(CHUNK_HEADER_SIZE + chunk.len());
+
+                datagram.extend_from_slice(&CHUNK_MAGIC);
+                datagram.extend_from_slice(&message_id.to_be_bytes());
+                // Both fit in a byte since there are at most 128 chunks
+                datagram.push(sequence_number as u8);
+                datagram.push(chunks.len() as u8);
+                datagram.extend_from_slice(chunk);
+
+                datagram
+            })
+            .collect())
+    }
+}
+
+impl Default for Gelf {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<
+        SeverityType: 'static + IsSeverity,
+        MessageType: HasSeverity<SeverityType> + HasText + HasFields + HasTimestamp,
+    > Format<SeverityType, MessageType> for Gelf
+{
+    fn format(&mut self, message: &MessageType, writer: &mut dyn io::Write) -> Result<()> {
+        let timestamp = message
+            .timestamp()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+
+        let mut object = Map::new();
+
+        object.insert("version".to_owned(), Value::from("1.1"));
+        object.insert("host".to_owned(), Value::from(self.hostname.as_str()));
+        object.insert("short_message".to_owned(), Value::from(message.text()));
+        object.insert(
+            "timestamp".to_owned(),
+            Value::from(timestamp.as_millis() as f64 / 1000.0),
+        );
+        object.insert(
+            "level".to_owned(),
+            Value::from(severity_level(message.severity()).unwrap_or(DEFAULT_LEVEL)),
+        );
+
+        for (key, value) in message.fields() {
+            if key != "id" {
+                object.insert(format!("_{}", key), Value::from(value.as_str()));
+            }
+        }
+
+        serde_json::to_writer(writer, &Value::Object(object))?;
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "gelf"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{FromCoreFields, Message};
+
+    fn format_to_value(message: &Message<Severity>) -> Value {
+        let mut message = message.clone();
+
+        message._timestamp = UNIX_EPOCH + Duration::from_millis(1_696_075_200_250);
+
+        let mut buffer = Vec::new();
+
+        Gelf::new()
+            .format(&message, &mut buffer)
+            .expect("formatting should succeed");
+
+        serde_json::from_slice(&buffer).expect("output should be valid JSON")
+    }
+
+    #[test]
+    fn core_fields() {
+        let message = Message::from_core_fields(Severity::Error, "hello, world");
+
+        let object = format_to_value(&message);
+
+        assert_eq!(object["version"], "1.1");
+        assert_eq!(object["host"], gethostname().to_string_lossy().as_ref());
+        assert_eq!(object["short_message"], "hello, world");
+        assert_eq!(object["timestamp"], 1_696_075_200.25);
+        assert_eq!(object["level"], 3);
+    }
+
+    #[test]
+    fn additional_fields() {
+        let mut message = Message::from_core_fields(Severity::Info, "hello, world");
+
+        message.fields_mut().extend([
+            ("user_id".to_owned(), "42".to_owned()),
+            ("id".to_owned(), "reserved".to_owned()),
+        ]);
+
+        let object = format_to_value(&message);
+
+        assert_eq!(object["_user_id"], "42");
+        assert!(object.get("_id").is_none());
+    }
+
+    #[test]
+    fn chunk_small_payload() {
+        assert_eq!(Gelf::chunk(b"small", 1, 100).unwrap(), [b"small".to_vec()]);
+    }
+
+    #[test]
+    fn chunk_large_payload() {
+        let payload = (0..=255).collect::<Vec<u8>>();
+
+        let datagrams = Gelf::chunk(&payload, 0x0102030405060708, 112).unwrap();
+
+        assert_eq!(datagrams.len(), 3);
+
+        for (sequence_number, datagram) in datagrams.iter().enumerate() {
+            assert_eq!(datagram[..2], CHUNK_MAGIC);
+            assert_eq!(datagram[2..10], [1, 2, 3, 4, 5, 6, 7, 8]);
+            assert_eq!(datagram[10], sequence_number as u8);
+            assert_eq!(datagram[11], 3);
+        }
+
+        let reassembled = datagrams
+            .iter()
+            .flat_map(|datagram| datagram[CHUNK_HEADER_SIZE..].iter().copied())
+            .collect::<Vec<_>>();
+
+        assert_eq!(reassembled, payload);
+    }
+
+    #[test]
+    fn chunk_too_many() {
+        assert!(Gelf::chunk(&[0; 129 * 100], 1, 100 + CHUNK_HEADER_SIZE).is_err());
+    }
+
+    #[test]
+    fn name() {
+        assert_eq!(
+            Format::<Severity, Message<Severity>>::name(&Gelf::new()),
+            "gelf"
+        );
+    }
+}
@@ -0,0 +1,458 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+use std::io;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::{Map, Value};
+
+use crate::{
+    traits::Format, HasCorrelationId, HasFields, HasId, HasSeverity, HasSourceLocation, HasTags,
+    HasText, HasTimestamp, IsSeverity, Result,
+};
+
+/// A formatter that outputs each message as a JSON object, for log aggregators that expect
+/// newline-delimited JSON.
+///
+/// Messages look like:
+///
+/// ```json
+/// {"correlation_id":"request-1","file":"src/main.rs","id":7,"line":12,"severity":"info","tags":["auth"],"text":"user logged in","timestamp":"2023-09-30T12:00:00.250Z","user_id":"42"}
+/// ```
+///
+/// Each object is followed by a newline. The timestamp is written in RFC 3339 format, and the
+/// correlation ID and source location are only included if the message has them. The keys can be renamed with
+/// [`Json::with_field_names`]. Structured fields are added to the top-level object, except for
+/// fields that would replace one of the keys above.
+///
+/// [`Format::format_metadata`] returns the same object without the text, for log shippers that
+/// index the metadata separately.
+///
+/// For log shippers that expect a JSON array instead, see [`Json::batch_mode`].
+///
+/// # Example
+///
+/// ```
+/// # use got_ur_logs_uwu::formatters::Json;
+/// #
+/// let formatter = Json::new();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Json {
+    field_names: JsonFieldNames,
+    /// The objects that have been formatted since the last flush, if batch mode is enabled.
+    batch: Option<Vec<Value>>,
+}
+
+/// The keys that [`Json`] writes the core fields of each message under.
+///
+/// # Example
+///
+/// ```
+/// # use got_ur_logs_uwu::formatters::JsonFieldNames;
+/// #
+/// let field_names = JsonFieldNames {
+///     severity: "level".to_owned(),
+///     text: "message".to_owned(),
+///     ..JsonFieldNames::default()
+/// };
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JsonFieldNames {
+    /// The key for the message's ID, `"id"` by default.
+    pub id: String,
+    /// The key for the message's severity, `"severity"` by default.
+    pub severity: String,
+    /// The key for the message's text, `"text"` by default.
+    pub text: String,
+    /// The key for when the message was emitted, `"timestamp"` by default.
+    pub timestamp: String,
+    /// The key for the source file the message was logged from, `"file"` by default.
+    pub file: String,
+    /// The key for the line number the message was logged from, `"line"` by default.
+    pub line: String,
+    /// The key for the message's tags, `"tags"` by default.
+    pub tags: String,
+    /// The key for the message's correlation ID, `"correlation_id"` by default.
+    pub correlation_id: String,
+}
+
+impl Default for JsonFieldNames {
+    fn default() -> Self {
+        Self {
+            id: "id".to_owned(),
+            severity: "severity".to_owned(),
+            text: "text".to_owned(),
+            timestamp: "timestamp".to_owned(),
+            file: "file".to_owned(),
+            line: "line".to_owned(),
+            tags: "tags".to_owned(),
+            correlation_id: "correlation_id".to_owned(),
+        }
+    }
+}
+
+impl Json {
+    /// Creates a new JSON formatter.
+    pub fn new() -> Self {
+        Self {
+            field_names: JsonFieldNames::default(),
+            batch: None,
+        }
+    }
+
+    /// Renames the keys that the core fields of each message are written under.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_names` - The keys to use
+    pub fn with_field_names(mut self, field_names: JsonFieldNames) -> Self {
+        self.field_names = field_names;
+        self
+    }
+
+    /// Enables or disables batch mode, which is disabled by default.
+    ///
+    /// In batch mode, formatting a message only adds its object to a buffer. When the writer is
+    /// flushed, all of the buffered objects are written as a single JSON array, like
+    /// `[{...},{...}]` followed by a newline, and the buffer is cleared. Nothing is written if no messages were
+    /// formatted since the last flush.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to buffer messages until the writer is flushed
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::{
+    /// #     formatters::Json, writers::ConsoleWriter, FromCoreFields, Message, Severity, Write
+    /// # };
+    /// #
+    /// let mut buffer = Vec::new();
+    ///
+    /// {
+    ///     let mut writer = ConsoleWriter::new_write(&mut buffer, Json::new().batch_mode(true));
+    ///
+    ///     writer.write(&Message::from_core_fields(Severity::Info, "first")).unwrap();
+    ///     writer.write(&Message::from_core_fields(Severity::Info, "second")).unwrap();
+    ///     writer.flush().unwrap();
+    /// }
+    ///
+    /// assert!(String::from_utf8(buffer).unwrap().starts_with('['));
+    /// ```
+    pub fn batch_mode(mut self, enabled: bool) -> Self {
+        self.batch = enabled.then(Vec::new);
+        self
+    }
+
+    /// Builds the JSON object for a message.
+    fn object<
+        SeverityType: IsSeverity,
+        MessageType: HasSeverity<SeverityType>
+            + HasText
+            + HasFields
+            + HasId
+            + HasTimestamp
+            + HasSourceLocation
+            + HasTags
+            + HasCorrelationId,
+    >(
+        &self,
+        message: &MessageType,
+    ) -> Map<String, Value> {
+        let mut object = Map::new();
+
+        object.insert(self.field_names.id.clone(), Value::from(message.id()));
+        object.insert(
+            self.field_names.severity.clone(),
+            Value::from(message.severity().to_string()),
+        );
+        object.insert(self.field_names.text.clone(), Value::from(message.text()));
+
+        object.insert(
+            self.field_names.timestamp.clone(),
+            Value::from(
+                DateTime::<Utc>::from(message.timestamp())
+                    .to_rfc3339_opts(SecondsFormat::Millis, true),
+            ),
+        );
+
+        if let Some(source_file) = message.source_file() {
+            object.insert(self.field_names.file.clone(), Value::from(source_file));
+        }
+
+        if let Some(source_line) = message.source_line() {
+            object.insert(self.field_names.line.clone(), Value::from(source_line));
+        }
+
+        object.insert(self.field_names.tags.clone(), Value::from(message.tags()));
+
+        if let Some(correlation_id) = message.correlation_id() {
+            object.insert(
+                self.field_names.correlation_id.clone(),
+                Value::from(correlation_id),
+            );
+        }
+
+        for (key, value) in message.fields() {
+            object
+                .entry(key.as_str())
+                .or_insert_with(|| Value::from(value.as_str()));
+        }
+
+        object
+    }
+}
+
+impl<
+        SeverityType: IsSeverity,
+        MessageType: HasSeverity<SeverityType>
+            + HasText
+            + HasFields
+            + HasId
+            + HasTimestamp
+            + HasSourceLocation
+            + HasTags
+            + HasCorrelationId,
+    > Format<SeverityType, MessageType> for Json
+{
+    fn format(&mut self, message: &MessageType, mut writer: &mut dyn io::Write) -> Result<()> {
+        self.format_generic(message, &mut writer)
+    }
+
+    fn format_generic<WriterType: io::Write>(
+        &mut self,
+        message: &MessageType,
+        writer: &mut WriterType,
+    ) -> Result<()> {
+        let object = self.object(message);
+
+        match &mut self.batch {
+            Some(batch) => batch.push(Value::Object(object)),
+            None => {
+                serde_json::to_writer(&mut *writer, &object)?;
+                writer.write_all(b"\n")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn format_metadata(&mut self, message: &MessageType) -> Result<String> {
+        let mut object = self.object(message);
+
+        object.remove(&self.field_names.text);
+
+        Ok(serde_json::to_string(&object)?)
+    }
+
+    fn flush(&mut self, writer: &mut dyn io::Write) -> Result<()> {
+        if let Some(batch) = self.batch.as_mut().filter(|batch| !batch.is_empty()) {
+            serde_json::to_writer(&mut *writer, &Value::Array(std::mem::take(batch)))?;
+            writer.write_all(b"\n")?;
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "json"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        str::FromStr,
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    use super::*;
+    use crate::{FromCoreFields, Message, Severity};
+
+    fn format_to_value(message: &Message<Severity>) -> Value {
+        let mut buffer = Vec::new();
+
+        Json::new()
+            .format(message, &mut buffer)
+            .expect("formatting should succeed");
+
+        Value::from_str(&String::from_utf8(buffer).expect("output should be valid UTF-8"))
+            .expect("output should be valid JSON")
+    }
+
+    #[test]
+    fn core_fields() {
+        let message = Message::from_core_fields(Severity::Warning, "disk \"almost\" full\n");
+
+        let object = format_to_value(&message);
+
+        assert_eq!(object["id"], message.id());
+        assert_eq!(object["severity"], "warning");
+        assert_eq!(object["text"], "disk \"almost\" full\n");
+    }
+
+    #[test]
+    fn ends_with_newline() {
+        let mut buffer = Vec::new();
+
+        Json::new()
+            .format(
+                &Message::from_core_fields(Severity::Info, "hello, world"),
+                &mut buffer,
+            )
+            .expect("formatting should succeed");
+
+        let output = String::from_utf8(buffer).unwrap();
+
+        assert!(output.ends_with("}\n"));
+        assert_eq!(output.lines().count(), 1);
+    }
+
+    #[test]
+    fn timestamp_and_source_location() {
+        let mut message = Message::from_core_fields(Severity::Info, "hello, world");
+
+        message._timestamp = UNIX_EPOCH + Duration::from_millis(1_696_075_200_250);
+        message._source_file = Some("src/main.rs");
+        message._source_line = Some(12);
+
+        let object = format_to_value(&message);
+
+        assert_eq!(object["timestamp"], "2023-09-30T12:00:00.250Z");
+        assert_eq!(object["file"], "src/main.rs");
+        assert_eq!(object["line"], 12);
+    }
+
+    #[test]
+    fn no_source_location() {
+        let object = format_to_value(&Message::from_core_fields(Severity::Info, "hello, world"));
+
+        assert!(object.get("timestamp").is_some());
+        assert!(object.get("file").is_none());
+        assert!(object.get("line").is_none());
+        assert_eq!(object["tags"], serde_json::json!([]));
+        assert!(object.get("correlation_id").is_none());
+    }
+
+    #[test]
+    fn metadata() {
+        let mut message = Message::from_core_fields(Severity::Warning, "disk almost full");
+
+        message
+            .fields_mut()
+            .push(("disk".to_owned(), "/dev/sda1".to_owned()));
+        message._tags = vec!["storage".to_owned(), "alert".to_owned()];
+        message._correlation_id = Some("request-1".to_owned());
+
+        let metadata = Json::new()
+            .format_metadata(&message)
+            .expect("formatting should succeed");
+
+        let object = Value::from_str(&metadata).expect("metadata should be valid JSON");
+
+        assert_eq!(object["id"], message.id());
+        assert_eq!(object["severity"], "warning");
+        assert_eq!(object["disk"], "/dev/sda1");
+        assert_eq!(object["tags"], serde_json::json!(["storage", "alert"]));
+        assert_eq!(object["correlation_id"], "request-1");
+        assert!(object.get("timestamp").is_some());
+        assert!(object.get("text").is_none());
+        assert!(!metadata.ends_with('\n'));
+    }
+
+    #[test]
+    fn fields() {
+        let mut message = Message::from_core_fields(Severity::Info, "user logged in");
+
+        message.fields_mut().extend([
+            ("user_id".to_owned(), "42".to_owned()),
+            ("text".to_owned(), "reserved".to_owned()),
+        ]);
+
+        let object = format_to_value(&message);
+
+        assert_eq!(object["user_id"], "42");
+        assert_eq!(object["text"], "user logged in");
+    }
+
+    #[test]
+    fn field_names() {
+        let message = Message::from_core_fields(Severity::Info, "user logged in");
+
+        let mut buffer = Vec::new();
+
+        Json::new()
+            .with_field_names(JsonFieldNames {
+                severity: "level".to_owned(),
+                text: "message".to_owned(),
+                ..JsonFieldNames::default()
+            })
+            .format(&message, &mut buffer)
+            .expect("formatting should succeed");
+
+        let object = Value::from_str(&String::from_utf8(buffer).unwrap()).unwrap();
+
+        assert_eq!(object["id"], message.id());
+        assert_eq!(object["level"], "info");
+        assert_eq!(object["message"], "user logged in");
+        assert!(object.get("text").is_none());
+    }
+
+    #[test]
+    fn batch_mode() {
+        let mut formatter = Json::new().batch_mode(true);
+        let mut buffer = Vec::new();
+
+        for text in ["first", "second"] {
+            Format::<Severity, Message<Severity>>::format(
+                &mut formatter,
+                &Message::from_core_fields(Severity::Info, text),
+                &mut buffer,
+            )
+            .expect("formatting should succeed");
+        }
+
+        assert!(buffer.is_empty());
+
+        Format::<Severity, Message<Severity>>::flush(&mut formatter, &mut buffer)
+            .expect("flushing should succeed");
+
+        let buffer_last_byte = buffer.last().copied();
+        let array = Value::from_str(&String::from_utf8(buffer).unwrap()).unwrap();
+
+        assert_eq!(array.as_array().map(Vec::len), Some(2));
+        assert_eq!(array[0]["text"], "first");
+        assert_eq!(array[1]["text"], "second");
+        assert_eq!(buffer_last_byte, Some(b'\n'));
+
+        // The batch is reset, so flushing again writes nothing
+        let mut buffer = Vec::new();
+
+        Format::<Severity, Message<Severity>>::flush(&mut formatter, &mut buffer)
+            .expect("flushing should succeed");
+
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn name() {
+        assert_eq!(
+            Format::<Severity, Message<Severity>>::name(&Json::new()),
+            "json"
+        );
+    }
+}
@@ -0,0 +1,193 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+use std::io;
+
+use crate::{traits::Format, HasFields, HasSeverity, HasText, IsSeverity, Result};
+
+/// A formatter that outputs messages in the [logfmt](https://brandur.org/logfmt) format.
+///
+/// Messages look like:
+///
+/// ```plaintext
+/// severity=info text="user logged in" user_id=42
+/// ```
+///
+/// Values are quoted when they are empty or contain spaces, quotes, or equals signs.
+///
+/// # Example
+///
+/// ```
+/// # use got_ur_logs_uwu::formatters::Logfmt;
+/// #
+/// let formatter = Logfmt::new();
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Logfmt;
+
+impl Logfmt {
+    /// Creates a new logfmt formatter.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Formats a message to any kind of writer, so that it can be shared by [`Format::format`] and
+    /// [`Format::format_generic`].
+    fn format_to<
+        SeverityType: IsSeverity,
+        MessageType: HasSeverity<SeverityType> + HasText + HasFields,
+        WriterType: io::Write + ?Sized,
+    >(
+        message: &MessageType,
+        writer: &mut WriterType,
+    ) -> Result<()> {
+        write!(writer, "severity=")?;
+        write_value(writer, &message.severity().to_string())?;
+        write!(writer, " text=")?;
+        write_value(writer, message.text())?;
+
+        for (key, value) in message.fields() {
+            write!(writer, " {}=", key)?;
+            write_value(writer, value)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes a value, quoting and escaping it if needed.
+///
+/// Control characters are escaped so that a value can never break a message across lines.
+fn write_value<WriterType: io::Write + ?Sized>(
+    writer: &mut WriterType,
+    value: &str,
+) -> io::Result<()> {
+    let needs_quotes = value.is_empty()
+        || value.chars().any(|character| {
+            character.is_whitespace()
+                || character.is_control()
+                || character == '"'
+                || character == '='
+        });
+
+    if !needs_quotes {
+        return write!(writer, "{}", value);
+    }
+
+    write!(writer, "\"")?;
+
+    for character in value.chars() {
+        match character {
+            '\\' => write!(writer, "\\\\")?,
+            '"' => write!(writer, "\\\"")?,
+            '\n' => write!(writer, "\\n")?,
+            '\r' => write!(writer, "\\r")?,
+            '\t' => write!(writer, "\\t")?,
+            character if character.is_control() => {
+                write!(writer, "\\u{:04x}", u32::from(character))?
+            }
+            character => write!(writer, "{}", character)?,
+        }
+    }
+
+    write!(writer, "\"")
+}
+
+impl<SeverityType: IsSeverity, MessageType: HasSeverity<SeverityType> + HasText + HasFields>
+    Format<SeverityType, MessageType> for Logfmt
+{
+    fn format(&mut self, message: &MessageType, writer: &mut dyn io::Write) -> Result<()> {
+        Self::format_to(message, writer)
+    }
+
+    fn format_generic<WriterType: io::Write>(
+        &mut self,
+        message: &MessageType,
+        writer: &mut WriterType,
+    ) -> Result<()> {
+        Self::format_to(message, writer)
+    }
+
+    fn name(&self) -> &'static str {
+        "logfmt"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FromCoreFields, Message, Severity};
+
+    fn format_to_string(message: &Message<Severity>) -> String {
+        let mut buffer = Vec::new();
+
+        Logfmt::new()
+            .format(message, &mut buffer)
+            .expect("formatting should succeed");
+
+        String::from_utf8(buffer).expect("output should be valid UTF-8")
+    }
+
+    #[test]
+    fn core_fields() {
+        let message = Message::from_core_fields(Severity::Info, "started");
+
+        assert_eq!(format_to_string(&message), "severity=info text=started");
+    }
+
+    #[test]
+    fn quotes_values() {
+        let message =
+            Message::from_core_fields(Severity::DeveloperWarning, "user \"bob\" logged in");
+
+        assert_eq!(
+            format_to_string(&message),
+            "severity=\"dev warning\" text=\"user \\\"bob\\\" logged in\""
+        );
+    }
+
+    #[test]
+    fn escapes_control_characters() {
+        let message = Message::from_core_fields(Severity::Info, "line one\nline two\r\tend\u{1b}");
+
+        assert_eq!(
+            format_to_string(&message),
+            "severity=info text=\"line one\\nline two\\r\\tend\\u001b\""
+        );
+    }
+
+    #[test]
+    fn fields() {
+        let mut message = Message::from_core_fields(Severity::Info, "user logged in");
+
+        message.fields_mut().extend([
+            ("user_id".to_owned(), "42".to_owned()),
+            ("note".to_owned(), "".to_owned()),
+        ]);
+
+        assert_eq!(
+            format_to_string(&message),
+            "severity=info text=\"user logged in\" user_id=42 note=\"\""
+        );
+    }
+
+    #[test]
+    fn name() {
+        assert_eq!(
+            Format::<Severity, Message<Severity>>::name(&Logfmt::new()),
+            "logfmt"
+        );
+    }
+}
@@ -0,0 +1,157 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+use std::io;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use gethostname::gethostname;
+use serde_json::{Map, Value};
+
+use crate::{traits::Format, HasFields, HasSeverity, HasText, HasTimestamp, IsSeverity, Result};
+
+/// A formatter that outputs messages as [Logstash](https://www.elastic.co/logstash) JSON events.
+///
+/// Messages look like:
+///
+/// ```json
+/// {"@timestamp":"2023-09-30T12:00:00.000Z","@version":"1","host":"server-1","level":"info","message":"hello, world"}
+/// ```
+///
+/// The `@timestamp` key is when the message was emitted. Structured fields are added to the top-level object, except for fields that would replace one
+/// of the keys above.
+///
+/// # Example
+///
+/// ```
+/// # use got_ur_logs_uwu::formatters::Logstash;
+/// #
+/// let formatter = Logstash::new();
+/// ```
+pub struct Logstash {
+    hostname: String,
+}
+
+impl Logstash {
+    /// Creates a new Logstash formatter.
+    ///
+    /// Every event is tagged with the current machine's hostname as its `host` key.
+    pub fn new() -> Self {
+        Self {
+            hostname: gethostname().to_string_lossy().into_owned(),
+        }
+    }
+}
+
+impl Default for Logstash {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<
+        SeverityType: IsSeverity,
+        MessageType: HasSeverity<SeverityType> + HasText + HasFields + HasTimestamp,
+    > Format<SeverityType, MessageType> for Logstash
+{
+    fn format(&mut self, message: &MessageType, writer: &mut dyn io::Write) -> Result<()> {
+        let timestamp = DateTime::<Utc>::from(message.timestamp());
+
+        let mut event = Map::new();
+
+        event.insert(
+            "@timestamp".to_owned(),
+            Value::from(timestamp.to_rfc3339_opts(SecondsFormat::Millis, true)),
+        );
+        event.insert("@version".to_owned(), Value::from("1"));
+        event.insert("host".to_owned(), Value::from(self.hostname.as_str()));
+        event.insert(
+            "level".to_owned(),
+            Value::from(message.severity().to_string()),
+        );
+        event.insert("message".to_owned(), Value::from(message.text()));
+
+        for (key, value) in message.fields() {
+            event
+                .entry(key.as_str())
+                .or_insert_with(|| Value::from(value.as_str()));
+        }
+
+        serde_json::to_writer(writer, &Value::Object(event))?;
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "logstash"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use super::*;
+    use crate::{FromCoreFields, Message, Severity};
+
+    fn format_to_value(message: &Message<Severity>) -> Value {
+        let mut message = message.clone();
+
+        message._timestamp = SystemTime::UNIX_EPOCH + Duration::from_millis(1_696_075_200_250);
+
+        let mut buffer = Vec::new();
+
+        Logstash::new()
+            .format(&message, &mut buffer)
+            .expect("formatting should succeed");
+
+        serde_json::from_slice(&buffer).expect("output should be valid JSON")
+    }
+
+    #[test]
+    fn core_fields() {
+        let message = Message::from_core_fields(Severity::Warning, "hello, world");
+
+        let event = format_to_value(&message);
+
+        assert_eq!(event["@timestamp"], "2023-09-30T12:00:00.250Z");
+        assert_eq!(event["@version"], "1");
+        assert_eq!(event["host"], gethostname().to_string_lossy().as_ref());
+        assert_eq!(event["level"], "warning");
+        assert_eq!(event["message"], "hello, world");
+    }
+
+    #[test]
+    fn fields() {
+        let mut message = Message::from_core_fields(Severity::Info, "hello, world");
+
+        message.fields_mut().extend([
+            ("user_id".to_owned(), "42".to_owned()),
+            ("message".to_owned(), "ignored".to_owned()),
+        ]);
+
+        let event = format_to_value(&message);
+
+        assert_eq!(event["user_id"], "42");
+        assert_eq!(event["message"], "hello, world");
+    }
+
+    #[test]
+    fn name() {
+        assert_eq!(
+            Format::<Severity, Message<Severity>>::name(&Logstash::new()),
+            "logstash"
+        );
+    }
+}
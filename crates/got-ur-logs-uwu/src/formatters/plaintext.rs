@@ -13,10 +13,15 @@
 // You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
 // not, see <https://www.gnu.org/licenses/>.
 
-use std::collections::HashMap;
+use std::{any::Any, collections::HashMap, process, sync::Arc};
 
-use crate::{traits::Format, HasSeverity, HasText, IsSeverity, Result};
-use handlebars::Handlebars;
+use crate::{
+    traits::Format, HasFields, HasSeverity, HasSourceLocation, HasText, HasTimestamp, IsSeverity,
+    Result, Severity,
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use gethostname::gethostname;
+use handlebars::{Context, Handlebars, Helper, HelperResult, Output, RenderContext, Template};
 
 /// A formatter that outputs messages as plain text using a template.
 ///
@@ -25,12 +30,85 @@ use handlebars::Handlebars;
 /// # Example
 ///
 /// ```
-/// # import got_ur_logs_uwu::formatters::Plaintext;
+/// # use got_ur_logs_uwu::formatters::Plaintext;
 /// #
 /// let formatter = Plaintext::new_default();
 /// ```
 pub struct Plaintext {
     handlebars: Handlebars<'static>,
+    template: Arc<CompiledTemplate>,
+    color: bool,
+    pid: bool,
+    hostname: Option<String>,
+}
+
+/// A [`Plaintext`] template that has already been compiled.
+///
+/// Compiling a template takes much longer than cloning a compiled one, so formatters that use the
+/// same template can share it with [`Plaintext::from_compiled`] instead of each compiling it again.
+///
+/// # Example
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use got_ur_logs_uwu::formatters::{CompiledTemplate, Plaintext};
+/// #
+/// let template = Arc::new(CompiledTemplate::new("{{severity}}: {{text}}").expect("error in template"));
+///
+/// let stdout_formatter = Plaintext::from_compiled(template.clone());
+/// let file_formatter = Plaintext::from_compiled(template);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CompiledTemplate {
+    template: Template,
+}
+
+impl CompiledTemplate {
+    /// The name that templates are registered under.
+    const NAME: &'static str = "plaintext";
+
+    /// Compiles a template.
+    ///
+    /// See [`Plaintext::new`] for the template format.
+    ///
+    /// # Arguments
+    ///
+    /// * `template_string` - The template to compile
+    ///
+    /// # Returns
+    ///
+    /// Returns the compiled template, or an error if the template is invalid.
+    pub fn new<StringType: AsRef<str>>(template_string: StringType) -> Result<Self> {
+        Ok(Self {
+            template: Template::compile_with_name(template_string, Self::NAME.to_owned())?,
+        })
+    }
+}
+
+const ANSI_RESET: &str = "\x1b[0m";
+const ANSI_TRACE: &str = "\x1b[90m";
+const ANSI_DEBUG: &str = "\x1b[36m";
+const ANSI_DEVELOPER_WARNING: &str = "\x1b[35m";
+const ANSI_INFO: &str = "\x1b[32m";
+const ANSI_WARNING: &str = "\x1b[33m";
+const ANSI_ERROR: &str = "\x1b[31m";
+const ANSI_FATAL: &str = "\x1b[1;31m";
+
+/// Gets the ANSI color code for a severity, if it is one of the levels of the built-in [`Severity`]
+/// type.
+fn severity_color(severity: &dyn Any) -> Option<&'static str> {
+    severity
+        .downcast_ref::<Severity>()
+        .and_then(|severity| match severity {
+            Severity::Trace => Some(ANSI_TRACE),
+            Severity::Debug => Some(ANSI_DEBUG),
+            Severity::DeveloperWarning => Some(ANSI_DEVELOPER_WARNING),
+            Severity::Info => Some(ANSI_INFO),
+            Severity::Warning => Some(ANSI_WARNING),
+            Severity::Error => Some(ANSI_ERROR),
+            Severity::Fatal => Some(ANSI_FATAL),
+            Severity::Custom(..) => None,
+        })
 }
 
 impl Plaintext {
@@ -45,13 +123,13 @@ impl Plaintext {
     /// This will result in messages that look like:
     ///
     /// ```plaintext
-    /// [INFO] hello, world
+    /// [info] hello, world
     /// ```
     ///
     /// # Example
     ///
     /// ```
-    /// # import got_ur_logs_uwu::formatters::Plaintext;
+    /// # use got_ur_logs_uwu::formatters::Plaintext;
     /// #
     /// let formatter = Plaintext::new("{{severity}}: {{text}}").expect("error in template");
     /// ```
@@ -59,12 +137,74 @@ impl Plaintext {
     /// # Template variables
     ///
     /// You can use the following variables in your template strings:
-    /// * `severity`: The severity of the message, written like `'INFO'` or `'DEV WARNING'`
+    /// * `severity`: The severity of the message, written like `'info'` or `'dev warning'`, see
+    ///   [`Severity::INFO_STR`] and the other constants for the names of the built-in severities
+    /// * `severity_upper`: The severity of the message in uppercase, like `'INFO'`
+    /// * `severity_lower`: The severity of the message in lowercase, like `'info'`
     /// * `text`: The message text
+    /// * `fields`: The structured fields of the message, written like `'key1=value1 key2=value2'`
+    /// * `timestamp`: When the message was emitted in RFC 3339 format, like
+    ///   `'2023-09-30T12:00:00.250Z'`
+    /// * `source_file`, `source_line`, `source_module`: Where the message was logged from, only if
+    ///   the message has a source location
+    /// * `pid`: The ID of the current process, only if enabled with [`Plaintext::with_pid`]
+    /// * `hostname`: The name of the current host, only if enabled with
+    ///   [`Plaintext::with_hostname`]
     pub fn new<StringType: AsRef<str>>(template_string: StringType) -> Result<Self> {
+        Ok(Self::from_compiled(Arc::new(CompiledTemplate::new(
+            template_string,
+        )?)))
+    }
+
+    /// Creates a new plaintext formatter using a template that has already been compiled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::formatters::Plaintext;
+    /// #
+    /// let formatter = Plaintext::new("{{severity}}: {{text}}").expect("error in template");
+    ///
+    /// // Reuses the template without compiling it again
+    /// let other_formatter = Plaintext::from_compiled(formatter.compiled_template());
+    /// ```
+    pub fn from_compiled(template: Arc<CompiledTemplate>) -> Self {
         let mut handlebars = Handlebars::new();
-        handlebars.register_template_string("plaintext", template_string)?;
-        Ok(Self { handlebars })
+        // Handlebars escapes HTML by default, which is not wanted for plain text
+        handlebars.register_escape_fn(handlebars::no_escape);
+        handlebars.register_template(CompiledTemplate::NAME, template.template.clone());
+        Self {
+            handlebars,
+            template,
+            color: false,
+            pid: false,
+            hostname: None,
+        }
+    }
+
+    /// Gets the compiled template of the formatter so that it can be shared with other
+    /// formatters, see [`Plaintext::from_compiled`].
+    pub fn compiled_template(&self) -> Arc<CompiledTemplate> {
+        self.template.clone()
+    }
+
+    /// Creates a new plaintext formatter using the given template in strict mode.
+    ///
+    /// In strict mode, formatting a message returns an error if the template uses a variable that
+    /// is missing, instead of rendering it as an empty string or the null value. This is useful for
+    /// catching typos in templates. See [`Plaintext::new`] for the template format.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::formatters::Plaintext;
+    /// #
+    /// let formatter = Plaintext::new_strict("{{severity}}: {{text}}").expect("error in template");
+    /// ```
+    pub fn new_strict<StringType: AsRef<str>>(template_string: StringType) -> Result<Self> {
+        let mut formatter = Self::new(template_string)?;
+        formatter.handlebars.set_strict_mode(true);
+        Ok(formatter)
     }
 
     /// Creates a new plaintext formatter using the default template.
@@ -72,13 +212,13 @@ impl Plaintext {
     /// This will result in messages that look like:
     ///
     /// ```plaintext
-    /// [INFO] hello, world
+    /// [info] hello, world
     /// ```
     ///
     /// # Example
     ///
     /// ```
-    /// # import got_ur_logs_uwu::formatters::Plaintext;
+    /// # use got_ur_logs_uwu::formatters::Plaintext;
     /// #
     /// let formatter = Plaintext::new_default();
     /// ```
@@ -86,19 +226,512 @@ impl Plaintext {
         Self::new("[{{severity}}] {{text}}")
             .expect("template error when creating default formatter")
     }
+
+    /// Sets whether the severity should be colored with ANSI escape codes.
+    ///
+    /// When enabled, the `severity`, `severity_upper`, and `severity_lower` template variables are
+    /// wrapped in a color that depends on the severity. Only the built-in [`Severity`] type is
+    /// colored, other severity types are output as-is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::formatters::Plaintext;
+    /// #
+    /// let formatter = Plaintext::new_default().with_color(true);
+    /// ```
+    pub fn with_color(mut self, enabled: bool) -> Self {
+        self.color = enabled;
+        self
+    }
+
+    /// Sets whether the `pid` template variable is set to the ID of the current process.
+    ///
+    /// This is useful to tell apart messages when the logs of multiple processes are merged. It is
+    /// disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::formatters::Plaintext;
+    /// #
+    /// let formatter = Plaintext::new("[{{pid}}] [{{severity}}] {{text}}")
+    ///     .expect("error in template")
+    ///     .with_pid(true);
+    /// ```
+    pub fn with_pid(mut self, enabled: bool) -> Self {
+        self.pid = enabled;
+        self
+    }
+
+    /// Sets whether the `hostname` template variable is set to the name of the current host.
+    ///
+    /// This is useful to correlate messages across machines when logs are aggregated. The hostname
+    /// is looked up once when this is called since it does not change. It is disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::formatters::Plaintext;
+    /// #
+    /// let formatter = Plaintext::new("{{hostname}} [{{severity}}] {{text}}")
+    ///     .expect("error in template")
+    ///     .with_hostname(true);
+    /// ```
+    pub fn with_hostname(mut self, enabled: bool) -> Self {
+        self.hostname = if enabled {
+            Some(gethostname().to_string_lossy().into_owned())
+        } else {
+            None
+        };
+        self
+    }
+
+    /// Sets the string that template variables render as when they are missing from a message.
+    ///
+    /// By default, missing variables render as an empty string. Setting a placeholder like `"-"`
+    /// keeps output aligned when optional variables, like `hostname`, are not enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::formatters::Plaintext;
+    /// #
+    /// let formatter = Plaintext::new("{{hostname}} [{{severity}}] {{text}}")
+    ///     .expect("error in template")
+    ///     .with_null_value("-");
+    /// ```
+    pub fn with_null_value<StringType: Into<String>>(mut self, null_value: StringType) -> Self {
+        let null_value = null_value.into();
+
+        self.handlebars.register_helper(
+            "helperMissing",
+            Box::new(
+                move |_: &Helper,
+                      _: &Handlebars,
+                      _: &Context,
+                      _: &mut RenderContext,
+                      output: &mut dyn Output|
+                      -> HelperResult {
+                    output.write(&null_value)?;
+                    Ok(())
+                },
+            ),
+        );
+
+        self
+    }
 }
 
-impl<SeverityType: IsSeverity, MessageType: HasSeverity<SeverityType> + HasText>
-    Format<SeverityType, MessageType> for Plaintext
+impl<
+        SeverityType: 'static + IsSeverity,
+        MessageType: HasSeverity<SeverityType> + HasText + HasFields + HasTimestamp + HasSourceLocation,
+    > Format<SeverityType, MessageType> for Plaintext
 {
     fn format(&mut self, message: &MessageType, writer: &mut dyn std::io::Write) -> Result<()> {
         let mut data = HashMap::new();
 
-        data.insert("severity", message.severity().to_string());
+        let severity = message.severity().to_string();
+
+        let color = if self.color {
+            severity_color(message.severity())
+        } else {
+            None
+        };
+
+        let colorize = |value: String| match color {
+            Some(color) => format!("{}{}{}", color, value, ANSI_RESET),
+            None => value,
+        };
+
+        data.insert("severity_upper", colorize(severity.to_uppercase()));
+        data.insert("severity_lower", colorize(severity.to_lowercase()));
+        data.insert("severity", colorize(severity));
         data.insert("text", message.text().to_owned());
 
+        data.insert(
+            "fields",
+            message
+                .fields()
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, value))
+                .collect::<Vec<_>>()
+                .join(" "),
+        );
+        data.insert(
+            "timestamp",
+            DateTime::<Utc>::from(message.timestamp()).to_rfc3339_opts(SecondsFormat::Millis, true),
+        );
+
+        if let Some(source_file) = message.source_file() {
+            data.insert("source_file", source_file.to_owned());
+        }
+
+        if let Some(source_line) = message.source_line() {
+            data.insert("source_line", source_line.to_string());
+        }
+
+        if let Some(source_module) = message.source_module() {
+            data.insert("source_module", source_module.to_owned());
+        }
+
+        if self.pid {
+            data.insert("pid", process::id().to_string());
+        }
+
+        if let Some(hostname) = &self.hostname {
+            data.insert("hostname", hostname.clone());
+        }
+
         self.handlebars
-            .render_to_write("plaintext", &data, writer)
+            .render_to_write(CompiledTemplate::NAME, &data, writer)
             .map_err(|e| e.into())
     }
+
+    fn supports_color(&self) -> bool {
+        self.color
+    }
+
+    fn name(&self) -> &'static str {
+        "plaintext"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use super::*;
+    use crate::{private::MessageBuilder, BuildMessage, Error, FromCoreFields, Message, Severity};
+
+    fn format_to_string(formatter: &mut Plaintext, message: &Message<Severity>) -> String {
+        let mut buffer = Vec::new();
+
+        formatter
+            .format(message, &mut buffer)
+            .expect("formatting should succeed");
+
+        String::from_utf8(buffer).expect("output should be valid UTF-8")
+    }
+
+    #[test]
+    fn from_compiled() {
+        let template = Plaintext::new("{{severity_upper}}: {{text}}")
+            .unwrap()
+            .compiled_template();
+
+        let mut formatter = Plaintext::from_compiled(template.clone());
+
+        assert!(Arc::ptr_eq(&formatter.compiled_template(), &template));
+        assert_eq!(
+            format_to_string(
+                &mut formatter,
+                &Message::from_core_fields(Severity::Info, "hello, world")
+            ),
+            "INFO: hello, world"
+        );
+    }
+
+    #[test]
+    fn custom_message_type() {
+        struct CustomMessage {
+            severity: Severity,
+            text: String,
+            fields: Vec<(String, String)>,
+        }
+
+        impl HasSeverity<Severity> for CustomMessage {
+            fn severity(&self) -> &Severity {
+                &self.severity
+            }
+        }
+
+        impl HasText for CustomMessage {
+            fn text(&self) -> &str {
+                &self.text
+            }
+        }
+
+        impl HasFields for CustomMessage {
+            fn fields(&self) -> &[(String, String)] {
+                &self.fields
+            }
+
+            fn fields_mut(&mut self) -> &mut Vec<(String, String)> {
+                &mut self.fields
+            }
+        }
+
+        impl HasTimestamp for CustomMessage {
+            fn timestamp(&self) -> SystemTime {
+                UNIX_EPOCH + Duration::from_millis(1_696_075_200_250)
+            }
+        }
+
+        impl HasSourceLocation for CustomMessage {
+            fn source_file(&self) -> Option<&'static str> {
+                Some("src/custom.rs")
+            }
+
+            fn source_line(&self) -> Option<u32> {
+                Some(7)
+            }
+
+            fn source_module(&self) -> Option<&'static str> {
+                None
+            }
+        }
+
+        let mut formatter = Plaintext::new(
+            "{{timestamp}} {{source_file}}:{{source_line}} {{source_module}} [{{severity}}] \
+             {{text}} {{fields}}",
+        )
+        .unwrap()
+        .with_null_value("-");
+
+        let mut buffer = Vec::new();
+
+        formatter
+            .format(
+                &CustomMessage {
+                    severity: Severity::Info,
+                    text: "hello, world".to_owned(),
+                    fields: vec![("user_id".to_owned(), "42".to_owned())],
+                },
+                &mut buffer,
+            )
+            .expect("formatting should succeed");
+
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "2023-09-30T12:00:00.250Z src/custom.rs:7 - [info] hello, world user_id=42"
+        );
+    }
+
+    #[test]
+    fn default_template() {
+        let mut formatter = Plaintext::new_default();
+
+        let message = Message::from_core_fields(Severity::Info, "hello, world");
+
+        assert_eq!(
+            format_to_string(&mut formatter, &message),
+            "[info] hello, world"
+        );
+    }
+
+    #[test]
+    fn timestamp() {
+        let mut formatter = Plaintext::new("{{timestamp}} {{text}}").unwrap();
+
+        let message: Message<Severity> = MessageBuilder {
+            severity: Some(Severity::Info),
+            text: Some("hello, world".to_owned()),
+            timestamp: Some(UNIX_EPOCH + Duration::from_millis(1_696_075_200_250)),
+            ..Default::default()
+        }
+        .build();
+
+        assert_eq!(
+            format_to_string(&mut formatter, &message),
+            "2023-09-30T12:00:00.250Z hello, world"
+        );
+    }
+
+    #[test]
+    fn source_location() {
+        let mut formatter =
+            Plaintext::new("{{source_module}} {{source_file}}:{{source_line}} {{text}}")
+                .unwrap()
+                .with_null_value("?");
+
+        let message: Message<Severity> = MessageBuilder {
+            severity: Some(Severity::Info),
+            text: Some("hello, world".to_owned()),
+            ..MessageBuilder::at_source_location("src/main.rs", 42, "app")
+        }
+        .build();
+
+        assert_eq!(
+            format_to_string(&mut formatter, &message),
+            "app src/main.rs:42 hello, world"
+        );
+        assert_eq!(
+            format_to_string(
+                &mut formatter,
+                &Message::from_core_fields(Severity::Info, "hello, world")
+            ),
+            "? ?:? hello, world"
+        );
+    }
+
+    #[test]
+    fn severity_case() {
+        let mut formatter =
+            Plaintext::new("{{severity}} {{severity_upper}} {{severity_lower}}").unwrap();
+
+        let message = Message::from_core_fields(Severity::DeveloperWarning, "hello, world");
+
+        assert_eq!(
+            format_to_string(&mut formatter, &message),
+            "dev warning DEV WARNING dev warning"
+        );
+    }
+
+    #[test]
+    fn does_not_escape_html() {
+        let mut formatter = Plaintext::new_default();
+
+        let message = Message::from_core_fields(Severity::Info, "<a & b>");
+
+        assert_eq!(format_to_string(&mut formatter, &message), "[info] <a & b>");
+    }
+
+    #[test]
+    fn fields() {
+        let mut formatter = Plaintext::new("{{text}} {{fields}}").unwrap();
+
+        let mut message = Message::from_core_fields(Severity::Info, "hello, world");
+
+        message.fields_mut().extend([
+            ("a".to_owned(), "1".to_owned()),
+            ("b".to_owned(), "2".to_owned()),
+        ]);
+
+        assert_eq!(
+            format_to_string(&mut formatter, &message),
+            "hello, world a=1 b=2"
+        );
+    }
+
+    #[test]
+    fn no_fields() {
+        let mut formatter = Plaintext::new("{{text}} {{fields}}").unwrap();
+
+        let message = Message::from_core_fields(Severity::Info, "hello, world");
+
+        assert_eq!(format_to_string(&mut formatter, &message), "hello, world ");
+    }
+
+    #[test]
+    fn default_metadata() {
+        let mut formatter = Plaintext::new_default();
+
+        let message = Message::from_core_fields(Severity::Info, "hello, world");
+
+        assert_eq!(formatter.format_metadata(&message).unwrap(), "{}");
+    }
+
+    #[test]
+    fn does_not_support_color() {
+        let formatter = Plaintext::new_default();
+
+        assert!(!Format::<Severity, Message<Severity>>::supports_color(
+            &formatter
+        ));
+    }
+
+    #[test]
+    fn color() {
+        let mut formatter = Plaintext::new_default().with_color(true);
+
+        let message = Message::from_core_fields(Severity::Error, "hello, world");
+
+        assert!(Format::<Severity, Message<Severity>>::supports_color(
+            &formatter
+        ));
+        assert_eq!(
+            format_to_string(&mut formatter, &message),
+            "[\x1b[31merror\x1b[0m] hello, world"
+        );
+    }
+
+    #[test]
+    fn pid() {
+        let mut formatter = Plaintext::new("{{pid}}").unwrap().with_pid(true);
+
+        let message = Message::from_core_fields(Severity::Info, "hello, world");
+
+        assert_eq!(
+            format_to_string(&mut formatter, &message),
+            process::id().to_string()
+        );
+    }
+
+    #[test]
+    fn pid_disabled() {
+        let mut formatter = Plaintext::new("{{pid}}").unwrap().with_null_value("-");
+
+        let message = Message::from_core_fields(Severity::Info, "hello, world");
+
+        assert_eq!(format_to_string(&mut formatter, &message), "-");
+    }
+
+    #[test]
+    fn hostname() {
+        let mut formatter = Plaintext::new("{{hostname}}").unwrap().with_hostname(true);
+
+        let message = Message::from_core_fields(Severity::Info, "hello, world");
+
+        assert_eq!(
+            format_to_string(&mut formatter, &message),
+            gethostname().to_string_lossy()
+        );
+    }
+
+    #[test]
+    fn strict_mode() {
+        let mut formatter = Plaintext::new_strict("[{{severity}}] {{text}}").unwrap();
+
+        let message = Message::from_core_fields(Severity::Info, "hello, world");
+
+        assert_eq!(
+            format_to_string(&mut formatter, &message),
+            "[info] hello, world"
+        );
+    }
+
+    #[test]
+    fn strict_mode_missing_variable() {
+        let mut formatter = Plaintext::new_strict("{{missing}} {{text}}")
+            .unwrap()
+            .with_null_value("-");
+
+        let message = Message::from_core_fields(Severity::Info, "hello, world");
+
+        let mut buffer = Vec::new();
+
+        assert!(matches!(
+            formatter.format(&message, &mut buffer),
+            Err(Error::HandlebarsRenderError(_))
+        ));
+    }
+
+    #[test]
+    fn missing_variable_renders_empty() {
+        let mut formatter = Plaintext::new("{{missing}} {{text}}").unwrap();
+
+        let message = Message::from_core_fields(Severity::Info, "hello, world");
+
+        assert_eq!(format_to_string(&mut formatter, &message), " hello, world");
+    }
+
+    #[test]
+    fn missing_variable_renders_null_value() {
+        let mut formatter = Plaintext::new("{{missing}} {{text}}")
+            .unwrap()
+            .with_null_value("-");
+
+        let message = Message::from_core_fields(Severity::Info, "hello, world");
+
+        assert_eq!(format_to_string(&mut formatter, &message), "- hello, world");
+    }
+
+    #[test]
+    fn name() {
+        assert_eq!(
+            Format::<Severity, Message<Severity>>::name(&Plaintext::new_default()),
+            "plaintext"
+        );
+    }
 }
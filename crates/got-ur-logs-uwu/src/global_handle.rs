@@ -0,0 +1,195 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+use std::sync::{PoisonError, RwLock};
+
+use crate::{
+    FromCoreFields, HasDebugSeverity, HasDeveloperWarningSeverity, HasErrorSeverity,
+    HasFatalSeverity, HasInfoSeverity, HasSeverity, HasText, HasTraceSeverity, HasWarningSeverity,
+    IsSeverity, Logger, Severity,
+};
+
+/// Logs text to the installed global logger, with the severity given as a built-in [`Severity`].
+type LogFn = fn(Severity, &str);
+
+/// The function that logs to the global logger installed with [`GlobalLoggerHandle::install`].
+static INSTALLED: RwLock<Option<LogFn>> = RwLock::new(None);
+
+/// A handle to the global logger that does not depend on its severity and message types.
+///
+/// Libraries cannot call [`Logger::global`] without knowing the severity and message types that
+/// the application uses. Instead, the application installs its global logger with
+/// [`GlobalLoggerHandle::install`] and libraries log through [`GlobalLoggerHandle::get`]. Each
+/// built-in severity is converted to the application's severity type with the `Has*Severity`
+/// traits.
+///
+/// # Example
+///
+/// ```
+/// # use got_ur_logs_uwu::{GlobalLoggerHandle, Message, Severity};
+/// #
+/// // In the application
+/// GlobalLoggerHandle::install::<Severity, Message<Severity>>();
+///
+/// // In a library
+/// if let Some(logger) = GlobalLoggerHandle::get() {
+///     logger.log_info("hello, world");
+/// }
+/// ```
+#[derive(Clone, Copy)]
+pub struct GlobalLoggerHandle {
+    log: LogFn,
+}
+
+impl GlobalLoggerHandle {
+    /// Installs the global logger with the given severity and message types so that it can be
+    /// reached through [`GlobalLoggerHandle::get`].
+    ///
+    /// This replaces any global logger that was installed before.
+    pub fn install<SeverityType, MessageType>()
+    where
+        SeverityType: 'static
+            + IsSeverity
+            + HasTraceSeverity
+            + HasDebugSeverity
+            + HasDeveloperWarningSeverity
+            + HasInfoSeverity
+            + HasWarningSeverity
+            + HasErrorSeverity
+            + HasFatalSeverity,
+        MessageType: 'static + HasSeverity<SeverityType> + HasText + FromCoreFields<SeverityType>,
+    {
+        *INSTALLED.write().unwrap_or_else(PoisonError::into_inner) =
+            Some(log_to_global::<SeverityType, MessageType>);
+    }
+
+    /// Gets a handle to the installed global logger.
+    ///
+    /// # Returns
+    ///
+    /// Returns the handle, or `None` if the application has not installed a global logger.
+    pub fn get() -> Option<Self> {
+        INSTALLED
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .map(|log| Self { log })
+    }
+
+    /// Logs a message with a given severity.
+    ///
+    /// Custom severities are logged with the closest built-in severity below them.
+    ///
+    /// # Arguments
+    ///
+    /// * `severity` - The severity of the message
+    /// * `text` - The text content of the message
+    pub fn log_with_severity(&self, severity: Severity, text: &str) {
+        (self.log)(severity, text);
+    }
+
+    /// Logs a trace message.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text content of the message
+    pub fn log_trace(&self, text: &str) {
+        self.log_with_severity(Severity::Trace, text);
+    }
+
+    /// Logs a debug message.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text content of the message
+    pub fn log_debug(&self, text: &str) {
+        self.log_with_severity(Severity::Debug, text);
+    }
+
+    /// Logs a developer warning message.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text content of the message
+    pub fn log_developer_warning(&self, text: &str) {
+        self.log_with_severity(Severity::DeveloperWarning, text);
+    }
+
+    /// Logs an info message.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text content of the message
+    pub fn log_info(&self, text: &str) {
+        self.log_with_severity(Severity::Info, text);
+    }
+
+    /// Logs a warning message.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text content of the message
+    pub fn log_warning(&self, text: &str) {
+        self.log_with_severity(Severity::Warning, text);
+    }
+
+    /// Logs an error message.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text content of the message
+    pub fn log_error(&self, text: &str) {
+        self.log_with_severity(Severity::Error, text);
+    }
+
+    /// Logs a fatal error message.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text content of the message
+    pub fn log_fatal(&self, text: &str) {
+        self.log_with_severity(Severity::Fatal, text);
+    }
+}
+
+/// Logs to the global logger with the given severity and message types.
+fn log_to_global<SeverityType, MessageType>(severity: Severity, text: &str)
+where
+    SeverityType: 'static
+        + IsSeverity
+        + HasTraceSeverity
+        + HasDebugSeverity
+        + HasDeveloperWarningSeverity
+        + HasInfoSeverity
+        + HasWarningSeverity
+        + HasErrorSeverity
+        + HasFatalSeverity,
+    MessageType: 'static + HasSeverity<SeverityType> + HasText + FromCoreFields<SeverityType>,
+{
+    let logger = Logger::<SeverityType, MessageType>::global();
+
+    let severity =
+        Severity::from_index(severity.as_index()).expect("index of a severity should be valid");
+
+    match severity {
+        Severity::Trace => logger.log_trace(text),
+        Severity::Debug => logger.log_debug(text),
+        Severity::DeveloperWarning => logger.log_developer_warning(text),
+        Severity::Info => logger.log_info(text),
+        Severity::Warning => logger.log_warning(text),
+        Severity::Error => logger.log_error(text),
+        Severity::Fatal => logger.log_fatal(text),
+        Severity::Custom(..) => unreachable!("built-in severities are never custom"),
+    }
+}
@@ -28,7 +28,7 @@
 //! };
 //!
 //! // Initialize the global logger
-//! Logger::<Severity, Message<Severity>>::global()
+//! Logger::<Severity, Message<Severity>>::global_mut()
 //!     .add_writer(
 //!         ConsoleWriter::new_stdout(
 //!             Plaintext::new_default()
@@ -39,24 +39,48 @@
 //! log_info!("hello, world");
 //! ```
 
+#[cfg(feature = "tokio")]
+mod background;
+mod clock;
 mod errors;
+#[cfg(feature = "flexi-logger")]
+mod flexi_logger_bridge;
+mod global_handle;
 mod logger;
+mod logger_pool;
 mod macros;
 mod message;
+mod rate_limiter;
 mod severity;
+#[cfg(test)]
+mod test_utils;
 mod traits;
 
 pub mod formatters;
+pub mod middleware;
+pub mod prelude;
 #[doc(hidden)]
 pub mod private;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod writers;
 
+#[cfg(feature = "tokio")]
+pub use background::BackgroundLogger;
+pub use clock::{Clock, SystemClock};
 pub use errors::{Error, Result};
-pub use logger::Logger;
+#[cfg(feature = "flexi-logger")]
+pub use flexi_logger_bridge::FlexiLogWriter;
+pub use global_handle::GlobalLoggerHandle;
+pub use logger::{Logger, LoggerSnapshot, WriterErrorPolicy, WriterHandle};
+pub use logger_pool::LoggerPool;
 pub use message::Message;
-pub use severity::Severity;
+pub use severity::{NumericSeverity, Severity};
+#[cfg(feature = "tokio")]
+pub use traits::AsyncWrite;
 pub use traits::{
-    FromCoreFields, HasDebugSeverity, HasDeveloperWarningSeverity, HasErrorSeverity,
-    HasFatalSeverity, HasInfoSeverity, HasSeverity, HasText, HasTraceSeverity, HasWarningSeverity,
-    IsSeverity, Write,
+    BuildMessage, Format, FromCoreFields, HasCoreFields, HasCorrelationId, HasDebugSeverity,
+    HasDeveloperWarningSeverity, HasErrorSeverity, HasFatalSeverity, HasFields, HasId,
+    HasInfoSeverity, HasSeverity, HasSeverityMut, HasSourceLocation, HasTags, HasText, HasTextMut,
+    HasTimestamp, HasTraceSeverity, HasWarningSeverity, IsSeverity, Write,
 };
@@ -15,13 +15,24 @@
 
 use std::{
     any::Any,
-    sync::{Arc, Mutex},
+    error, fmt,
+    io::{self, Write as _},
+    panic,
+    sync::{
+        Arc, Mutex, MutexGuard, OnceLock, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard,
+        TryLockError, Weak,
+    },
+    thread,
+    time::{Instant, SystemTime},
 };
 
+#[cfg(feature = "tokio")]
+use crate::BackgroundLogger;
 use crate::{
-    FromCoreFields, HasDebugSeverity, HasDeveloperWarningSeverity, HasErrorSeverity,
-    HasFatalSeverity, HasInfoSeverity, HasSeverity, HasText, HasTraceSeverity, HasWarningSeverity,
-    IsSeverity, Write,
+    middleware::Middleware, rate_limiter::RateLimiter, writers::end_line, Clock, Error, Format,
+    FromCoreFields, HasCoreFields, HasDebugSeverity, HasDeveloperWarningSeverity, HasErrorSeverity,
+    HasFatalSeverity, HasFields, HasInfoSeverity, HasSeverity, HasText, HasTextMut,
+    HasTraceSeverity, HasWarningSeverity, IsSeverity, Result, SystemClock, Write,
 };
 
 /// The logger is the main interface for the library.
@@ -52,8 +63,276 @@ use crate::{
 /// ```
 pub struct Logger<Severity: IsSeverity, Message: HasSeverity<Severity> + HasText> {
     min_severity: Severity,
+    verbosity_shift: i32,
     // writers: Vec<RefCell<Rc<dyn Write<Severity, Message>>>>,
-    writers: Vec<Arc<Mutex<dyn Write<Severity, Message>>>>,
+    writers: Vec<WriterEntry<Severity, Message>>,
+    next_writer_id: usize,
+    paused: bool,
+    #[cfg(feature = "metrics")]
+    metrics_integration: bool,
+    clock: Box<dyn Clock>,
+    middleware: Vec<Box<dyn Middleware<Severity, Message>>>,
+    message_logged_hooks: Vec<MessageLoggedHook<Message>>,
+    writer_error_hooks: Vec<WriterErrorHook>,
+    writer_error_policy: WriterErrorPolicy,
+    rate_limiter: Option<RateLimiter>,
+    created_at: Instant,
+}
+
+/// What a logger does when one of its writers fails.
+///
+/// The policy only applies to errors that are not handled by hooks registered with
+/// [`Logger::on_writer_error`]. Writers fail when [`Write::write`] returns an error, or when a
+/// writer's mutex has been poisoned by a panic on another thread.
+///
+/// # Example
+///
+/// ```
+/// # use got_ur_logs_uwu::{Logger, Message, Severity, WriterErrorPolicy};
+/// #
+/// let logger = Logger::<Severity, Message<Severity>>::default()
+///     .with_writer_error_policy(WriterErrorPolicy::LogToStderr);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WriterErrorPolicy {
+    /// Silently drop the message for the failing writer and continue with the next writer.
+    Ignore,
+    /// Panic with a description of the error.
+    ///
+    /// While the thread is already panicking, errors are written to stderr like with
+    /// [`WriterErrorPolicy::LogToStderr`] instead, since panicking again would abort the process.
+    #[default]
+    Panic,
+    /// Write a best-effort plain text description of the error and the message directly to
+    /// stderr, without going through any writers, and continue with the next writer.
+    LogToStderr,
+}
+
+/// A hook registered with [`Logger::on_message_logged`].
+type MessageLoggedHook<Message> = Box<dyn Fn(&Message) + Send + Sync>;
+
+/// A hook registered with [`Logger::on_writer_error`].
+type WriterErrorHook = Box<dyn Fn(&Error) + Send + Sync>;
+
+/// A writer that has been added to a logger, along with the ID its handle refers to.
+struct WriterEntry<Severity: IsSeverity, Message: HasSeverity<Severity> + HasText> {
+    id: usize,
+    writer: WriterReference<Severity, Message>,
+    /// Overrides the logger's minimum severity for this writer, if it is set.
+    min_severity: Option<Severity>,
+}
+
+/// How a logger refers to one of its writers.
+enum WriterReference<Severity: IsSeverity, Message: HasSeverity<Severity> + HasText> {
+    /// The logger keeps the writer alive.
+    Strong(Arc<Mutex<dyn Write<Severity, Message> + Send>>),
+    /// The writer is owned elsewhere and the logger stops using it once it is dropped.
+    Weak(Weak<Mutex<dyn Write<Severity, Message> + Send>>),
+    /// The writer is split into a formatter and the output it writes to, which are locked
+    /// separately so that the output is not locked while messages are being formatted.
+    Split {
+        formatter: Mutex<Box<dyn Format<Severity, Message> + Send>>,
+        output: Arc<Mutex<dyn io::Write + Send>>,
+    },
+}
+
+impl<Severity: IsSeverity, Message: HasSeverity<Severity> + HasText>
+    WriterReference<Severity, Message>
+{
+    /// Writes a message to the writer.
+    ///
+    /// Returns `None` if the writer is no longer alive.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message to write
+    /// * `non_blocking` - Whether to fail instead of waiting if the writer is already locked
+    fn write(&self, message: &Message, non_blocking: bool) -> Option<Result<()>> {
+        match self {
+            Self::Strong(writer) => Some(Self::write_to(writer, message, non_blocking)),
+            Self::Weak(writer) => writer
+                .upgrade()
+                .map(|writer| Self::write_to(&writer, message, non_blocking)),
+            Self::Split { formatter, output } => {
+                let mut buffer = Vec::new();
+
+                // The formatter lock is released before the output is locked
+                let result = lock(formatter, "formatter", non_blocking)
+                    .and_then(|mut formatter| formatter.format(message, &mut buffer));
+
+                end_line(&mut buffer);
+
+                Some(result.and_then(|()| {
+                    lock(output, "output", non_blocking)
+                        .and_then(|mut output| output.write_all(&buffer).map_err(Error::from))
+                }))
+            }
+        }
+    }
+
+    /// Flushes the writer.
+    ///
+    /// Returns `None` if the writer is no longer alive.
+    fn flush(&self) -> Option<Result<()>> {
+        match self {
+            Self::Strong(writer) => Some(Self::flush_to(writer)),
+            Self::Weak(writer) => writer.upgrade().map(|writer| Self::flush_to(&writer)),
+            Self::Split { formatter, output } => {
+                let mut buffer = Vec::new();
+
+                let result = lock(formatter, "formatter", false)
+                    .and_then(|mut formatter| formatter.flush(&mut buffer));
+
+                end_line(&mut buffer);
+
+                Some(result.and_then(|()| {
+                    lock(output, "output", false).and_then(|mut output| {
+                        output
+                            .write_all(&buffer)
+                            .and_then(|()| output.flush())
+                            .map_err(Error::from)
+                    })
+                }))
+            }
+        }
+    }
+
+    /// Gets the name of the writer, see [`Write::name`].
+    ///
+    /// Split writers are named `"split"`. Returns `None` if the writer is no longer alive.
+    fn name(&self) -> Option<&'static str> {
+        match self {
+            Self::Strong(writer) => Some(Self::name_of(writer)),
+            Self::Weak(writer) => writer.upgrade().map(|writer| Self::name_of(&writer)),
+            Self::Split { .. } => Some("split"),
+        }
+    }
+
+    fn name_of(writer: &Mutex<dyn Write<Severity, Message> + Send>) -> &'static str {
+        // The name does not depend on whatever state a panicking writer left behind
+        writer.lock().unwrap_or_else(PoisonError::into_inner).name()
+    }
+
+    fn write_to(
+        writer: &Mutex<dyn Write<Severity, Message> + Send>,
+        message: &Message,
+        non_blocking: bool,
+    ) -> Result<()> {
+        lock(writer, "writer", non_blocking).and_then(|mut writer| writer.write(message))
+    }
+
+    fn flush_to(writer: &Mutex<dyn Write<Severity, Message> + Send>) -> Result<()> {
+        lock(writer, "writer", false).and_then(|mut writer| writer.flush())
+    }
+}
+
+/// Locks part of a writer.
+///
+/// In the hook set by [`Logger::set_as_panic_hook`], the writer that panicked may still be locked
+/// by this thread, and waiting for it would deadlock. So the hook locks without blocking, and
+/// anything that is already locked is skipped with an error instead.
+///
+/// # Arguments
+///
+/// * `mutex` - The mutex to lock
+/// * `what` - What the mutex guards, for error messages
+/// * `non_blocking` - Whether to fail instead of waiting if the mutex is already locked
+fn lock<'mutex, Value: ?Sized>(
+    mutex: &'mutex Mutex<Value>,
+    what: &str,
+    non_blocking: bool,
+) -> Result<MutexGuard<'mutex, Value>> {
+    let poisoned = || Error::WriterError(format!("{} mutex is poisoned", what));
+
+    if !non_blocking {
+        return mutex.lock().map_err(|_| poisoned());
+    }
+
+    match mutex.try_lock() {
+        Ok(guard) => Ok(guard),
+        Err(TryLockError::WouldBlock) => Err(Error::WriterError(format!(
+            "{} is already locked while panicking",
+            what
+        ))),
+        Err(TryLockError::Poisoned(_)) => Err(poisoned()),
+    }
+}
+
+/// A snapshot of a logger's configuration at a point in time.
+///
+/// It is returned by [`Logger::snapshot`] and is useful for debugging and for asserting that a
+/// logger was configured as expected.
+///
+/// # Example
+///
+/// ```
+/// # use got_ur_logs_uwu::{Logger, LoggerSnapshot, Message, Severity};
+/// #
+/// let logger = Logger::<Severity, Message<Severity>>::default();
+///
+/// assert_eq!(
+///     logger.snapshot(),
+///     LoggerSnapshot {
+///         min_severity: Severity::Trace,
+///         writer_count: 0,
+///         paused: false,
+///     }
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoggerSnapshot<Severity: IsSeverity> {
+    /// The minimum severity of messages that are passed along to the writers.
+    pub min_severity: Severity,
+    /// The number of writers that have been added to the logger.
+    pub writer_count: usize,
+    /// Whether the logger is paused.
+    pub paused: bool,
+}
+
+/// A handle to a writer that has been added to a logger.
+///
+/// It is returned when adding writers and can be used to remove the writer later on.
+///
+/// # Example
+///
+/// ```
+/// # use got_ur_logs_uwu::{
+/// #     writers::ConsoleWriter, Logger, Message, Severity, formatters::Plaintext
+/// # };
+/// #
+/// # let mut logger = Logger::<Severity, Message<Severity>>::default();
+/// #
+/// let handle = logger.add_writer(
+///     ConsoleWriter::new_stdout(
+///         Plaintext::new_default()
+///     )
+/// );
+///
+/// assert!(handle.is_alive(&logger));
+///
+/// logger.remove_writer(handle);
+///
+/// assert!(!handle.is_alive(&logger));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WriterHandle {
+    id: usize,
+}
+
+impl WriterHandle {
+    /// Checks whether the writer this handle refers to is still present in the logger.
+    ///
+    /// Handles are only meaningful for the logger that returned them.
+    ///
+    /// # Arguments
+    ///
+    /// * `logger` - The logger that the writer was added to
+    pub fn is_alive<Severity: IsSeverity, Message: HasSeverity<Severity> + HasText>(
+        &self,
+        logger: &Logger<Severity, Message>,
+    ) -> bool {
+        logger.writers.iter().any(|entry| entry.id == self.id)
+    }
 }
 
 impl<Severity: IsSeverity, Message: HasSeverity<Severity> + HasText> Default
@@ -62,33 +341,227 @@ impl<Severity: IsSeverity, Message: HasSeverity<Severity> + HasText> Default
     fn default() -> Self {
         Self {
             min_severity: Severity::min(),
+            verbosity_shift: 0,
             writers: Vec::new(),
+            next_writer_id: 0,
+            paused: false,
+            #[cfg(feature = "metrics")]
+            metrics_integration: false,
+            clock: Box::new(SystemClock),
+            middleware: Vec::new(),
+            message_logged_hooks: Vec::new(),
+            writer_error_hooks: Vec::new(),
+            writer_error_policy: WriterErrorPolicy::default(),
+            rate_limiter: None,
+            created_at: Instant::now(),
         }
     }
 }
 
+impl<Severity: IsSeverity + fmt::Debug, Message: HasSeverity<Severity> + HasText> fmt::Debug
+    for Logger<Severity, Message>
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Writers are trait objects that are not necessarily debuggable, so we only show how many
+        // there are
+        formatter
+            .debug_struct("Logger")
+            .field("min_severity", &self.min_severity)
+            .field("writer_count", &self.writers.len())
+            .field("paused", &self.paused)
+            .finish()
+    }
+}
+
 impl<Severity: IsSeverity, Message: HasSeverity<Severity> + HasText> Logger<Severity, Message> {
-    /// Get the default global logger instance.
+    /// Get the default global logger instance for logging to it.
     ///
-    /// This is used by the macros to log messages.
-    pub fn global() -> &'static mut Self {
-        static mut LOGGER: Option<Box<dyn Any>> = None;
+    /// This is used by the macros to log messages. The global logger is behind a lock, so the
+    /// returned guard should be dropped before calling [`Self::global_mut`] on the same thread.
+    pub fn global() -> RwLockReadGuard<'static, Self>
+    where
+        Self: 'static + Send + Sync,
+    {
+        Self::global_lock()
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
 
-        unsafe {
-            if LOGGER.is_none() {
-                LOGGER = Some(Box::<Self>::default())
-            }
+    /// Get the default global logger instance for configuring it.
+    ///
+    /// Nothing can log to the global logger while the returned guard is held, including the macros
+    /// on the same thread, which would deadlock. It is best used for a single statement.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::{Logger, Message, Severity};
+    /// #
+    /// Logger::<Severity, Message<Severity>>::global_mut().set_min_severity(Severity::Warning);
+    /// ```
+    pub fn global_mut() -> RwLockWriteGuard<'static, Self>
+    where
+        Self: 'static + Send + Sync,
+    {
+        Self::global_lock()
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
 
-            LOGGER
-                .as_mut()
-                .expect("LOGGER should have been initialized above")
-                .downcast_mut::<Self>()
-                .expect("Global logger can only ever have one type")
-        }
+    /// Gets the lock around the global logger, creating the logger on first use.
+    fn global_lock() -> &'static RwLock<Self>
+    where
+        Self: 'static + Send + Sync,
+    {
+        // Statics are shared between all instantiations of a generic function, so the logger is
+        // type-erased here and downcast back
+        static LOGGER: OnceLock<Box<dyn Any + Send + Sync>> = OnceLock::new();
+
+        LOGGER
+            .get_or_init(|| Box::new(RwLock::new(Self::default())))
+            .downcast_ref::<RwLock<Self>>()
+            .expect("Global logger can only ever have one type")
+    }
+
+    /// Replaces the clock that the logger uses to get the current time.
+    ///
+    /// By default this is [`SystemClock`]. The clock is what [`Logger::now`] returns and what the
+    /// global rate limit is measured against.
+    ///
+    /// # Arguments
+    ///
+    /// * `clock` - The clock to use
+    pub fn with_clock<ClockType: 'static + Clock>(mut self, clock: ClockType) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    /// Enables emitting a counter with the [`metrics`] crate for each message that is logged.
+    ///
+    /// Each logged message increments the `log_messages_total` counter, labeled with the
+    /// message's severity. Whichever metrics recorder is installed, such as a Prometheus exporter,
+    /// will receive the counts.
+    ///
+    /// Requires the `metrics` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::{Logger, Message, Severity};
+    /// #
+    /// let logger = Logger::<Severity, Message<Severity>>::default().with_metrics_integration();
+    ///
+    /// logger.log_info("hello, world"); // ← This increments log_messages_total{severity="info"}
+    /// ```
+    #[cfg(feature = "metrics")]
+    pub fn with_metrics_integration(mut self) -> Self {
+        self.metrics_integration = true;
+        self
+    }
+
+    /// Sets what the logger does when one of its writers fails.
+    ///
+    /// By default this is [`WriterErrorPolicy::Panic`]. See [`WriterErrorPolicy`] for details.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The policy to use
+    pub fn with_writer_error_policy(mut self, policy: WriterErrorPolicy) -> Self {
+        self.writer_error_policy = policy;
+        self
+    }
+
+    /// Gets the minimum severity of messages that are passed along to the writers.
+    pub fn min_severity(&self) -> &Severity {
+        &self.min_severity
+    }
+
+    /// Sets the minimum severity of messages that are passed along to the writers.
+    ///
+    /// This clears any verbosity delta set with [`Logger::set_verbosity_delta`], so that the new
+    /// minimum severity becomes the base for future deltas.
+    ///
+    /// # Arguments
+    ///
+    /// * `severity` - The least critical severity that is passed along
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::{Logger, Message, Severity};
+    /// #
+    /// // For example, when the configuration is reloaded
+    /// Logger::<Severity, Message<Severity>>::global_mut().set_min_severity(Severity::Warning);
+    /// ```
+    pub fn set_min_severity(&mut self, severity: Severity) {
+        self.min_severity = severity;
+        self.verbosity_shift = 0;
+    }
+
+    /// Limits how many messages per second the logger passes along to its writers.
+    ///
+    /// This protects against runaway log loops overwhelming I/O. The limit is enforced with a
+    /// token bucket that holds up to one second's worth of messages, so short bursts are allowed.
+    /// Messages over the limit are dropped and counted, see
+    /// [`Logger::rate_limited_message_count`].
+    ///
+    /// # Arguments
+    ///
+    /// * `max_per_second` - The average number of messages allowed per second
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::{Logger, Message, Severity};
+    /// #
+    /// # let mut logger = Logger::<Severity, Message<Severity>>::default();
+    /// #
+    /// logger.set_global_rate_limit(1000.0);
+    /// ```
+    pub fn set_global_rate_limit(&mut self, max_per_second: f64) {
+        self.rate_limiter = Some(RateLimiter::new(max_per_second, self.clock.now()));
+    }
+
+    /// Gets the number of messages that have been dropped because of the global rate limit.
+    ///
+    /// This is always `0` if [`Logger::set_global_rate_limit`] has not been called.
+    pub fn rate_limited_message_count(&self) -> u64 {
+        self.rate_limiter
+            .as_ref()
+            .map_or(0, |rate_limiter| rate_limiter.dropped_count())
+    }
+
+    /// Moves the logger into a background task and returns a handle for logging to it.
+    ///
+    /// This is the recommended way to log from async code, since writers can block while writing
+    /// and would otherwise stall the executor. See [`BackgroundLogger`] for details.
+    ///
+    /// Must be called from within a tokio runtime. Requires the `tokio` feature.
+    #[cfg(feature = "tokio")]
+    pub fn spawn_background_logger(self) -> BackgroundLogger<Severity, Message>
+    where
+        Severity: 'static,
+        Message: 'static + Send,
+    {
+        BackgroundLogger::spawn(self)
+    }
+
+    /// Gets the time that the logger was created.
+    ///
+    /// This is useful for calculating uptime, for example with `logger.created_at().elapsed()`.
+    pub fn created_at(&self) -> Instant {
+        self.created_at
+    }
+
+    /// Gets the current time according to the logger's clock.
+    pub fn now(&self) -> SystemTime {
+        self.clock.now()
     }
 
     /// Adds a writer to the logger.
     ///
+    /// Returns a [`WriterHandle`] that can be used to remove the writer later on.
+    ///
     /// # Example
     ///
     /// ```
@@ -104,12 +577,53 @@ impl<Severity: IsSeverity, Message: HasSeverity<Severity> + HasText> Logger<Seve
     ///     )
     /// );
     /// ```
-    pub fn add_writer<Writer: 'static + Write<Severity, Message>>(&mut self, writer: Writer) {
-        self.writers.push(Arc::new(Mutex::new(writer)));
+    pub fn add_writer<Writer: 'static + Write<Severity, Message> + Send>(
+        &mut self,
+        writer: Writer,
+    ) -> WriterHandle {
+        self.add_writer_shared(Arc::new(Mutex::new(writer)))
+    }
+
+    /// Adds a writer to the logger that only receives messages of at least a given severity.
+    ///
+    /// Messages must pass both the logger's minimum severity and the writer's. This lets one
+    /// logger send verbose messages to some writers and only important messages to others.
+    ///
+    /// Returns a [`WriterHandle`] that can be used to remove the writer later on.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer to add
+    /// * `min_severity` - The least critical severity that is passed along to the writer
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::{
+    /// #     writers::ConsoleWriter, Logger, Message, Severity, formatters::Plaintext
+    /// # };
+    /// #
+    /// # let mut logger = Logger::<Severity, Message<Severity>>::default();
+    /// #
+    /// // Everything goes to stdout, but only warnings and above go to stderr
+    /// logger.add_writer(ConsoleWriter::new_stdout(Plaintext::new_default()));
+    /// logger.add_writer_with_min_severity(
+    ///     ConsoleWriter::new_stderr(Plaintext::new_default()),
+    ///     Severity::Warning,
+    /// );
+    /// ```
+    pub fn add_writer_with_min_severity<Writer: 'static + Write<Severity, Message> + Send>(
+        &mut self,
+        writer: Writer,
+        min_severity: Severity,
+    ) -> WriterHandle {
+        self.add_writer_shared_with_min_severity(Arc::new(Mutex::new(writer)), min_severity)
     }
 
     /// Adds a shared writer instance to the logger.
     ///
+    /// Returns a [`WriterHandle`] that can be used to remove the writer later on.
+    ///
     /// # Example
     ///
     /// ```
@@ -130,178 +644,1992 @@ impl<Severity: IsSeverity, Message: HasSeverity<Severity> + HasText> Logger<Seve
     ///     writer
     /// );
     /// ```
-    pub fn add_writer_shared(&mut self, writer: Arc<Mutex<dyn Write<Severity, Message>>>) {
-        self.writers.push(writer);
+    pub fn add_writer_shared(
+        &mut self,
+        writer: Arc<Mutex<dyn Write<Severity, Message> + Send>>,
+    ) -> WriterHandle {
+        self.add_writer_reference(WriterReference::Strong(writer), None)
     }
 
-    /// Logs a message object.
+    /// Adds a shared writer instance to the logger that only receives messages of at least a
+    /// given severity.
+    ///
+    /// See [`Logger::add_writer_with_min_severity`] for details.
+    ///
+    /// Returns a [`WriterHandle`] that can be used to remove the writer later on.
     ///
     /// # Arguments
     ///
-    /// * `message` - The message object that will be passed along to the writers
-    pub fn log_message(&self, message: Message) {
-        if message.severity() >= &self.min_severity {
-            for writer in &self.writers {
-                writer
-                    .lock()
-                    .unwrap()
-                    .write(&message)
-                    .expect("Failed to write message");
-            }
-        }
+    /// * `writer` - The writer to add
+    /// * `min_severity` - The least critical severity that is passed along to the writer
+    pub fn add_writer_shared_with_min_severity(
+        &mut self,
+        writer: Arc<Mutex<dyn Write<Severity, Message> + Send>>,
+        min_severity: Severity,
+    ) -> WriterHandle {
+        self.add_writer_reference(WriterReference::Strong(writer), Some(min_severity))
     }
 
-    /// Logs a message with the core fields set
+    /// Adds a weak reference to a writer instance to the logger.
     ///
-    /// # Arguments
+    /// The logger does not keep the writer alive. Once all the strong references to it are
+    /// dropped, the logger skips it.
     ///
-    /// * `severity` - The severity of the message
-    /// * `text` - The text content of the message
-    pub fn log_with_severity(&self, severity: Severity, text: &str)
-    where
-        Message: FromCoreFields<Severity>,
-    {
-        self.log_message(Message::from_core_fields(severity, text));
+    /// Returns a [`WriterHandle`] that can be used to remove the writer later on.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::sync::{Arc, Mutex};
+    /// # use got_ur_logs_uwu::{
+    /// #     writers::ConsoleWriter, Logger, Message, Severity, formatters::Plaintext, Write
+    /// # };
+    /// #
+    /// # let mut logger = Logger::<Severity, Message<Severity>>::default();
+    /// #
+    /// let writer: Arc<Mutex<dyn Write<Severity, Message<Severity>> + Send>> = Arc::new(Mutex::new(
+    ///     ConsoleWriter::new_stdout(
+    ///         Plaintext::new_default()
+    ///     )
+    /// ));
+    ///
+    /// logger.add_writer_weak(Arc::downgrade(&writer));
+    ///
+    /// // The logger stops writing to the writer once it is dropped
+    /// drop(writer);
+    /// ```
+    pub fn add_writer_weak(
+        &mut self,
+        writer: Weak<Mutex<dyn Write<Severity, Message> + Send>>,
+    ) -> WriterHandle {
+        self.add_writer_reference(WriterReference::Weak(writer), None)
     }
 
-    /// Logs a trace message
+    /// Adds a writer that is split into a formatter and the output that it writes to.
+    ///
+    /// Messages are formatted before the output is locked, so the output is only locked while the
+    /// formatted bytes are written to it, followed by a newline. This reduces contention when the
+    /// output is shared, for example between several loggers or threads.
+    ///
+    /// Returns a [`WriterHandle`] that can be used to remove the writer later on.
     ///
     /// # Arguments
     ///
-    /// * `text` - The text content of the message
-    pub fn log_trace(&self, text: &str)
-    where
-        Message: FromCoreFields<Severity>,
-        Severity: HasTraceSeverity,
-    {
-        self.log_with_severity(Severity::trace_severity(), text);
+    /// * `formatter` - The formatter to use for messages
+    /// * `output` - Where to write the formatted messages
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::{io, sync::{Arc, Mutex}};
+    /// # use got_ur_logs_uwu::{Logger, Message, Severity, formatters::Plaintext};
+    /// #
+    /// # let mut logger = Logger::<Severity, Message<Severity>>::default();
+    /// #
+    /// let output = Arc::new(Mutex::new(io::stderr()));
+    ///
+    /// logger.add_split_writer(Plaintext::new_default(), output);
+    /// ```
+    pub fn add_split_writer<
+        FormatterType: 'static + Format<Severity, Message> + Send,
+        OutputType: 'static + io::Write + Send,
+    >(
+        &mut self,
+        formatter: FormatterType,
+        output: Arc<Mutex<OutputType>>,
+    ) -> WriterHandle {
+        self.add_writer_reference(
+            WriterReference::Split {
+                formatter: Mutex::new(Box::new(formatter)),
+                output,
+            },
+            None,
+        )
     }
 
-    /// Logs a debug message
+    fn add_writer_reference(
+        &mut self,
+        writer: WriterReference<Severity, Message>,
+        min_severity: Option<Severity>,
+    ) -> WriterHandle {
+        let id = self.next_writer_id;
+
+        self.next_writer_id += 1;
+        self.writers.push(WriterEntry {
+            id,
+            writer,
+            min_severity,
+        });
+
+        WriterHandle { id }
+    }
+
+    /// Removes a writer from the logger.
     ///
     /// # Arguments
     ///
-    /// * `text` - The text content of the message
-    pub fn log_debug(&self, text: &str)
-    where
-        Message: FromCoreFields<Severity>,
-        Severity: HasDebugSeverity,
+    /// * `handle` - The handle that was returned when the writer was added
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the writer was removed, or `false` if it was not present.
+    pub fn remove_writer(&mut self, handle: WriterHandle) -> bool {
+        let writer_count = self.writers.len();
+
+        self.writers.retain(|entry| entry.id != handle.id);
+
+        self.writers.len() != writer_count
+    }
+
+    /// Removes all writers from the logger.
+    ///
+    /// Messages logged afterwards are dropped until a new writer is added. This is mostly useful
+    /// for tests that share the global logger, so that writers left behind by one test do not
+    /// receive another test's messages.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::{writers::CaptureWriter, Logger, Message, Severity};
+    /// #
+    /// let mut logger = Logger::<Severity, Message<Severity>>::default();
+    ///
+    /// logger.add_writer(CaptureWriter::new());
+    /// logger.clear_writers();
+    ///
+    /// assert_eq!(logger.writer_count(), 0);
+    /// ```
+    pub fn clear_writers(&mut self) {
+        self.writers.clear();
+    }
+
+    /// Gets the number of writers that have been added to the logger.
+    ///
+    /// Writers that were added with [`Logger::add_writer_weak`] are counted even if they have
+    /// since been dropped, until they are removed.
+    pub fn writer_count(&self) -> usize {
+        self.writers.len()
+    }
+
+    /// Iterates over the names of the logger's writers, in the order they were added.
+    ///
+    /// Writers that were added with [`Logger::add_writer_weak`] and have since been dropped are
+    /// skipped. See [`Write::name`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::{
+    /// #     writers::ConsoleWriter, Logger, Message, Severity, formatters::Plaintext
+    /// # };
+    /// #
+    /// let mut logger = Logger::<Severity, Message<Severity>>::default();
+    ///
+    /// logger.add_writer(ConsoleWriter::new_stdout(Plaintext::new_default()));
+    ///
+    /// assert_eq!(logger.writers().collect::<Vec<_>>(), ["console"]);
+    /// ```
+    pub fn writers(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.writers.iter().filter_map(|entry| entry.writer.name())
+    }
+
+    /// Finds the first writer with a given name, see [`Write::name`].
+    ///
+    /// Split writers added with [`Logger::add_split_writer`] are never returned since they are
+    /// not a single [`Write`] implementation.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the writer to find
+    ///
+    /// # Returns
+    ///
+    /// Returns the writer, or `None` if no live writer has the given name.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::{writers::CaptureWriter, Logger, Message, Severity};
+    /// #
+    /// let mut logger = Logger::<Severity, Message<Severity>>::default();
+    ///
+    /// logger.add_writer(CaptureWriter::new());
+    ///
+    /// assert!(logger.find_writer_by_name("capture").is_some());
+    /// assert!(logger.find_writer_by_name("console").is_none());
+    /// ```
+    pub fn find_writer_by_name(
+        &self,
+        name: &str,
+    ) -> Option<Arc<Mutex<dyn Write<Severity, Message> + Send>>> {
+        self.writers.iter().find_map(|entry| {
+            let writer = match &entry.writer {
+                WriterReference::Strong(writer) => writer.clone(),
+                WriterReference::Weak(writer) => writer.upgrade()?,
+                WriterReference::Split { .. } => return None,
+            };
+
+            (WriterReference::name_of(&writer) == name).then_some(writer)
+        })
+    }
+
+    /// Adds middleware to the logger.
+    ///
+    /// Middleware runs on every message that passes the logger's minimum severity, in the order it
+    /// was added, before the message is passed along to the writers. See [`Middleware`] for more
+    /// details.
+    ///
+    /// # Arguments
+    ///
+    /// * `middleware` - The middleware to add
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::{HasTags, Logger, Message, Severity};
+    /// #
+    /// # let mut logger = Logger::<Severity, Message<Severity>>::default();
+    /// #
+    /// // Drop any messages tagged as noisy
+    /// logger.add_middleware(|message: Message<Severity>| {
+    ///     if message.tags().iter().any(|tag| tag == "noisy") {
+    ///         None
+    ///     } else {
+    ///         Some(message)
+    ///     }
+    /// });
+    /// ```
+    pub fn add_middleware<MiddlewareType: 'static + Middleware<Severity, Message>>(
+        &mut self,
+        middleware: MiddlewareType,
+    ) {
+        self.middleware.push(Box::new(middleware));
+    }
+
+    /// Prepends a prefix to the text of every message.
+    ///
+    /// This is useful for libraries that embed a logger, so that their messages can be told apart.
+    /// The prefix is part of the text, so it appears after the severity in formatted output. It is
+    /// added as middleware, so it applies after any middleware added before it.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The prefix to prepend
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::{Logger, Message, Severity};
+    /// #
+    /// let logger = Logger::<Severity, Message<Severity>>::default().with_prefix("[mylib] ");
+    ///
+    /// logger.log_info("hello, world"); // ← This has the text "[mylib] hello, world"
+    /// ```
+    pub fn with_prefix<StringType: Into<String>>(mut self, prefix: StringType) -> Self
+    where
+        Severity: 'static,
+        Message: 'static + HasTextMut,
     {
-        self.log_with_severity(Severity::debug_severity(), text);
+        let prefix = prefix.into();
+
+        self.add_middleware(move |mut message: Message| {
+            message.text_mut().insert_str(0, &prefix);
+            Some(message)
+        });
+
+        self
+    }
+
+    /// Registers a hook that is called every time a message is logged.
+    ///
+    /// Hooks are called for each message passed to [`Logger::log_message`] or
+    /// [`Logger::broadcast`], regardless of filtering. They are called before the message is checked
+    /// against the minimum severity and before it goes through the middleware, so they also see
+    /// messages that are never written. This is useful for observability integrations that only
+    /// need to know that a message was logged.
+    ///
+    /// # Arguments
+    ///
+    /// * `hook` - The function to call with each logged message
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::sync::atomic::{AtomicUsize, Ordering};
+    /// # use got_ur_logs_uwu::{Logger, Message, Severity};
+    /// #
+    /// # let mut logger = Logger::<Severity, Message<Severity>>::default();
+    /// #
+    /// static MESSAGE_COUNT: AtomicUsize = AtomicUsize::new(0);
+    ///
+    /// logger.on_message_logged(|_| {
+    ///     MESSAGE_COUNT.fetch_add(1, Ordering::Relaxed);
+    /// });
+    /// ```
+    pub fn on_message_logged<Hook: 'static + Fn(&Message) + Send + Sync>(&mut self, hook: Hook) {
+        self.message_logged_hooks.push(Box::new(hook));
+    }
+
+    /// Registers a hook that is called every time a writer fails to write a message.
+    ///
+    /// By default, the logger handles writer failures according to its [`WriterErrorPolicy`]. Once
+    /// any hooks are registered, the errors are passed along to the hooks instead and logging
+    /// continues with the next writer.
+    ///
+    /// # Arguments
+    ///
+    /// * `hook` - The function to call with each writer error
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::{Logger, Message, Severity};
+    /// #
+    /// # let mut logger = Logger::<Severity, Message<Severity>>::default();
+    /// #
+    /// logger.on_writer_error(|error| {
+    ///     eprintln!("unable to log: {}", error);
+    /// });
+    /// ```
+    pub fn on_writer_error<Hook: 'static + Fn(&Error) + Send + Sync>(&mut self, hook: Hook) {
+        self.writer_error_hooks.push(Box::new(hook));
+    }
+
+    /// Pauses the logger.
+    ///
+    /// While paused, all messages are discarded regardless of their severity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::{Logger, Message, Severity};
+    /// #
+    /// # let mut logger = Logger::<Severity, Message<Severity>>::default();
+    /// #
+    /// logger.pause();
+    ///
+    /// logger.log_info("hello, world"); // ← This is discarded
+    ///
+    /// logger.resume();
+    /// ```
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Resumes the logger after it has been paused with [`Logger::pause`].
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Checks whether the logger is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Takes a snapshot of the logger's current configuration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::{Logger, Message, Severity};
+    /// #
+    /// # let logger = Logger::<Severity, Message<Severity>>::default();
+    /// #
+    /// println!("{:?}", logger.snapshot());
+    /// ```
+    pub fn snapshot(&self) -> LoggerSnapshot<Severity>
+    where
+        Severity: Clone,
+    {
+        LoggerSnapshot {
+            min_severity: self.min_severity.clone(),
+            writer_count: self.writers.len(),
+            paused: self.paused,
+        }
+    }
+
+    /// Checks whether messages of a given severity would be passed along to the writers.
+    ///
+    /// This is always `false` while the logger is paused.
+    ///
+    /// # Arguments
+    ///
+    /// * `severity` - The severity to check
+    pub fn is_enabled(&self, severity: &Severity) -> bool {
+        !self.paused && severity >= &self.min_severity
+    }
+
+    /// Logs a message object.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message object that will be passed along to the writers
+    pub fn log_message(&self, message: Message) {
+        self.call_message_logged_hooks(&message);
+
+        if self.is_enabled(message.severity()) {
+            self.dispatch(message, true, true, false);
+        }
+    }
+
+    /// Logs a message object to all the writers, whatever the minimum severity of the logger or of
+    /// the writers is.
+    ///
+    /// This is intended for a small number of high-importance events, like startup and shutdown,
+    /// which must always be logged regardless of the configured verbosity. The message is written
+    /// even if the logger is paused and is not counted against the global rate limit. It still
+    /// goes through the logger's middleware, which can change or drop it.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message object that will be passed along to the writers
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::{FromCoreFields, Logger, Message, Severity};
+    /// #
+    /// # let mut logger = Logger::<Severity, Message<Severity>>::default();
+    /// #
+    /// logger.set_min_severity(Severity::Error);
+    ///
+    /// // This is still written
+    /// logger.broadcast(Message::from_core_fields(Severity::Info, "starting up"));
+    /// ```
+    pub fn broadcast(&self, message: Message) {
+        self.call_message_logged_hooks(&message);
+        self.dispatch(message, false, true, false);
+    }
+
+    /// Calls the hooks registered with [`Logger::on_message_logged`].
+    fn call_message_logged_hooks(&self, message: &Message) {
+        for hook in &self.message_logged_hooks {
+            hook(message);
+        }
+    }
+
+    /// Passes a message through the middleware and along to the writers.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message object that will be passed along to the writers
+    /// * `filtered` - Whether the message counts against the global rate limit and is only passed
+    ///   along to writers whose minimum severity it meets
+    /// * `report_errors` - Whether writer errors are handled, or silently ignored
+    /// * `non_blocking` - Whether writers that are already locked are skipped instead of waited for
+    fn dispatch(&self, message: Message, filtered: bool, report_errors: bool, non_blocking: bool) {
+        let Some(message) = self
+            .middleware
+            .iter()
+            .try_fold(message, |message, middleware| middleware.process(message))
+        else {
+            return;
+        };
+
+        if let (true, Some(rate_limiter)) = (filtered, &self.rate_limiter) {
+            if !rate_limiter.try_acquire(self.clock.now()) {
+                return;
+            }
+        }
+
+        for entry in &self.writers {
+            if let (true, Some(min_severity)) = (filtered, &entry.min_severity) {
+                if message.severity() < min_severity {
+                    continue;
+                }
+            }
+
+            if let (true, Some(Err(error))) =
+                (report_errors, entry.writer.write(&message, non_blocking))
+            {
+                self.handle_writer_error(&error, Some(&message));
+            }
+        }
+
+        #[cfg(feature = "metrics")]
+        if self.metrics_integration {
+            metrics::counter!("log_messages_total", "severity" => message.severity().to_string())
+                .increment(1);
+        }
+    }
+
+    /// Flushes all of the writers so that any messages they have buffered are written.
+    ///
+    /// This is useful before a checkpoint, like a test assertion, to make sure that everything
+    /// logged so far has been written. Writer errors are handled the same way as when logging.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::{Logger, Message, Severity};
+    /// #
+    /// # let mut logger = Logger::<Severity, Message<Severity>>::default();
+    /// #
+    /// logger.log_info("hello, world");
+    ///
+    /// logger.drain();
+    /// ```
+    pub fn drain(&self) {
+        for entry in &self.writers {
+            if let Some(Err(error)) = entry.writer.flush() {
+                self.handle_writer_error(&error, None);
+            }
+        }
+    }
+
+    /// Passes a writer error along to the hooks, or handles it according to the policy if there
+    /// are none.
+    fn handle_writer_error(&self, error: &Error, message: Option<&Message>) {
+        if !self.writer_error_hooks.is_empty() {
+            for hook in &self.writer_error_hooks {
+                hook(error);
+            }
+
+            return;
+        }
+
+        // Panicking again while the thread is already panicking would abort the process
+        let policy = match self.writer_error_policy {
+            WriterErrorPolicy::Panic if thread::panicking() => WriterErrorPolicy::LogToStderr,
+            policy => policy,
+        };
+
+        match policy {
+            WriterErrorPolicy::Ignore => {}
+            WriterErrorPolicy::Panic => panic!("Failed to write message: {}", error),
+            WriterErrorPolicy::LogToStderr => {
+                // If even stderr fails there is nowhere left to report it
+                let _ = match message {
+                    Some(message) => writeln!(
+                        io::stderr(),
+                        "Failed to write message: {}: [{}] {}",
+                        error,
+                        message.severity(),
+                        message.text()
+                    ),
+                    None => writeln!(io::stderr(), "Failed to write message: {}", error),
+                };
+            }
+        }
+    }
+
+    /// Logs a message object that is only constructed if its severity is enabled.
+    ///
+    /// This is useful when building the message is expensive, since `message_fn` is never called
+    /// for messages that would be filtered out anyway.
+    ///
+    /// # Arguments
+    ///
+    /// * `severity` - The severity of the message that `message_fn` will construct
+    /// * `message_fn` - A function that constructs the message object
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::{FromCoreFields, Logger, Message, Severity};
+    /// #
+    /// # let logger = Logger::<Severity, Message<Severity>>::default();
+    /// #
+    /// logger.log_if_enabled(&Severity::Debug, || {
+    ///     Message::from_core_fields(Severity::Debug, "hello, world")
+    /// });
+    /// ```
+    pub fn log_if_enabled<MessageFn: FnOnce() -> Message>(
+        &self,
+        severity: &Severity,
+        message_fn: MessageFn,
+    ) {
+        if self.is_enabled(severity) {
+            self.log_message(message_fn());
+        }
+    }
+
+    /// Logs a message trait object.
+    ///
+    /// This is an escape hatch for code that only has a trait object instead of the logger's
+    /// message type. The writers receive a new message of the logger's message type with the same
+    /// core fields, so any other fields of the trait object are not logged.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message to copy the core fields from
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::{FromCoreFields, HasCoreFields, Logger, Message, Severity};
+    /// #
+    /// # let logger = Logger::<Severity, Message<Severity>>::default();
+    /// #
+    /// let message: Box<dyn HasCoreFields<Severity>> =
+    ///     Box::new(Message::from_core_fields(Severity::Info, "hello, world"));
+    ///
+    /// logger.log_dyn(message.as_ref());
+    /// ```
+    pub fn log_dyn(&self, message: &dyn HasCoreFields<Severity>)
+    where
+        Severity: Clone,
+        Message: FromCoreFields<Severity>,
+    {
+        if self.is_enabled(message.severity()) {
+            self.log_message(Message::from_core_fields_at(
+                message.severity().clone(),
+                message.text(),
+                self.now(),
+            ));
+        }
+    }
+
+    /// Sets a panic hook that logs panics as fatal messages to this logger.
+    ///
+    /// The hook replaces any previous panic hook, including the default one that prints panics to
+    /// standard error, so panics are only reported through the logger's writers. Writer errors are
+    /// silently ignored while logging a panic, whatever the logger's [`WriterErrorPolicy`] is, since
+    /// panicking again from within the hook would abort the program.
+    ///
+    /// If a writer panics while it is writing a message, the panic is still logged to the other
+    /// writers. The writer that panicked is skipped since it is still locked.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::{Logger, Message, Severity};
+    /// #
+    /// let logger: &'static Logger<Severity, Message<Severity>> =
+    ///     Box::leak(Box::new(Logger::default()));
+    ///
+    /// logger.set_as_panic_hook();
+    /// ```
+    pub fn set_as_panic_hook(&'static self)
+    where
+        Severity: 'static + HasFatalSeverity,
+        Message: 'static + FromCoreFields<Severity>,
+    {
+        panic::set_hook(Box::new(move |info| {
+            let message = Message::from_core_fields_at(
+                Severity::fatal_severity(),
+                &info.to_string(),
+                self.now(),
+            );
+
+            self.call_message_logged_hooks(&message);
+
+            if self.is_enabled(message.severity()) {
+                self.dispatch(message, true, false, true);
+            }
+        }));
+    }
+
+    /// Logs a message with the core fields set
+    ///
+    /// # Arguments
+    ///
+    /// * `severity` - The severity of the message
+    /// * `text` - The text content of the message
+    pub fn log_with_severity(&self, severity: Severity, text: &str)
+    where
+        Message: FromCoreFields<Severity>,
+    {
+        self.log_message(Message::from_core_fields_at(severity, text, self.now()));
+    }
+
+    /// Logs a message with text and structured fields.
+    ///
+    /// # Arguments
+    ///
+    /// * `severity` - The severity of the message
+    /// * `text` - The text content of the message
+    /// * `fields` - The structured fields of the message
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::{Logger, Message, Severity};
+    /// #
+    /// # let logger = Logger::<Severity, Message<Severity>>::default();
+    /// #
+    /// logger.log_with_fields(
+    ///     Severity::Info,
+    ///     "user logged in",
+    ///     vec![("user_id".to_owned(), "42".to_owned())],
+    /// );
+    /// ```
+    pub fn log_with_fields(&self, severity: Severity, text: &str, fields: Vec<(String, String)>)
+    where
+        Message: FromCoreFields<Severity> + HasFields,
+    {
+        if !self.is_enabled(&severity) {
+            return;
+        }
+
+        let mut message = Message::from_core_fields_at(severity, text, self.now());
+
+        message.fields_mut().extend(fields);
+
+        self.log_message(message);
+    }
+
+    /// Logs a structured message made up of key-value pairs and no text.
+    ///
+    /// # Arguments
+    ///
+    /// * `severity` - The severity of the message
+    /// * `pairs` - The key-value pairs that make up the structured fields of the message
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::{Logger, Message, Severity};
+    /// #
+    /// # let logger = Logger::<Severity, Message<Severity>>::default();
+    /// #
+    /// logger.log_structured(Severity::Info, [("event", "login"), ("user_id", "42")]);
+    /// ```
+    pub fn log_structured<
+        Key: Into<String>,
+        Value: Into<String>,
+        Pairs: IntoIterator<Item = (Key, Value)>,
+    >(
+        &self,
+        severity: Severity,
+        pairs: Pairs,
+    ) where
+        Message: FromCoreFields<Severity> + HasFields,
+    {
+        if !self.is_enabled(&severity) {
+            return;
+        }
+
+        let mut message = Message::from_core_fields_at(severity, "", self.now());
+
+        message.fields_mut().extend(
+            pairs
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into())),
+        );
+
+        self.log_message(message);
+    }
+
+    /// Logs an error and its chain of causes as a structured error message.
+    ///
+    /// The error itself is stored in the `error` field, and each of its causes are stored in
+    /// `cause_0`, `cause_1`, etc.
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - The error to log
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::io;
+    /// # use got_ur_logs_uwu::{Logger, Message, Severity};
+    /// #
+    /// # let logger = Logger::<Severity, Message<Severity>>::default();
+    /// #
+    /// let error = io::Error::new(io::ErrorKind::NotFound, "config file not found");
+    ///
+    /// logger.log_structured_error(&error);
+    /// ```
+    pub fn log_structured_error(&self, error: &dyn error::Error)
+    where
+        Message: FromCoreFields<Severity> + HasFields,
+        Severity: HasErrorSeverity,
+    {
+        let mut pairs = vec![("error".to_owned(), error.to_string())];
+        let mut source = error.source();
+
+        while let Some(cause) = source {
+            pairs.push((format!("cause_{}", pairs.len() - 1), cause.to_string()));
+            source = cause.source();
+        }
+
+        self.log_structured(Severity::error_severity(), pairs);
+    }
+
+    /// Logs a trace message
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text content of the message
+    pub fn log_trace(&self, text: &str)
+    where
+        Message: FromCoreFields<Severity>,
+        Severity: HasTraceSeverity,
+    {
+        self.log_with_severity(Severity::trace_severity(), text);
+    }
+
+    /// Logs a debug message
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text content of the message
+    pub fn log_debug(&self, text: &str)
+    where
+        Message: FromCoreFields<Severity>,
+        Severity: HasDebugSeverity,
+    {
+        self.log_with_severity(Severity::debug_severity(), text);
+    }
+
+    /// Logs a developer warning message
+    ///
+    /// For the built-in [`crate::Severity`] type this is logged as a debug message when the
+    /// `dev-warnings` feature is disabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text content of the message
+    pub fn log_developer_warning(&self, text: &str)
+    where
+        Message: FromCoreFields<Severity>,
+        Severity: HasDeveloperWarningSeverity,
+    {
+        self.log_with_severity(Severity::developer_warning_severity(), text);
+    }
+
+    /// Logs an info message
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text content of the message
+    pub fn log_info(&self, text: &str)
+    where
+        Message: FromCoreFields<Severity>,
+        Severity: HasInfoSeverity,
+    {
+        self.log_with_severity(Severity::info_severity(), text);
+    }
+
+    /// Logs a warning message
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text content of the message
+    pub fn log_warning(&self, text: &str)
+    where
+        Message: FromCoreFields<Severity>,
+        Severity: HasWarningSeverity,
+    {
+        self.log_with_severity(Severity::warning_severity(), text);
+    }
+
+    /// Logs an error message
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text content of the message
+    pub fn log_error(&self, text: &str)
+    where
+        Message: FromCoreFields<Severity>,
+        Severity: HasErrorSeverity,
+    {
+        self.log_with_severity(Severity::error_severity(), text);
+    }
+
+    /// Logs a fatal error message
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text content of the message
+    pub fn log_fatal(&self, text: &str)
+    where
+        Message: FromCoreFields<Severity>,
+        Severity: HasFatalSeverity,
+    {
+        self.log_with_severity(Severity::fatal_severity(), text);
+    }
+}
+
+impl<Message: HasSeverity<crate::Severity> + HasText> Logger<crate::Severity, Message> {
+    /// Shifts the minimum severity by a number of levels from its base value.
+    ///
+    /// The base value is the minimum severity as it was last set with
+    /// [`Logger::set_min_severity`]. Negative deltas make the logger more verbose and positive
+    /// deltas make it less verbose, and the result is clamped between [`crate::Severity::Trace`]
+    /// and [`crate::Severity::Fatal`]. This is meant for command line flags like `-v`, `-vv` and
+    /// `-q`. Setting a new delta replaces the previous one rather than adding to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - How many levels to shift the minimum severity by
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::{Logger, Message, Severity};
+    /// #
+    /// # let mut logger = Logger::<Severity, Message<Severity>>::default();
+    /// #
+    /// logger.set_min_severity(Severity::Info);
+    ///
+    /// // -vv
+    /// logger.set_verbosity_delta(-2);
+    ///
+    /// assert_eq!(logger.min_severity(), &Severity::Debug);
+    /// ```
+    pub fn set_verbosity_delta(&mut self, delta: i32) {
+        let max_index = crate::Severity::Fatal.as_index() as i64;
+        let base_index = self.min_severity.as_index() as i64 - self.verbosity_shift as i64;
+        let index = (base_index + delta as i64).clamp(0, max_index);
+
+        self.min_severity = crate::Severity::from_index(index as usize)
+            .expect("index should have been clamped to a valid severity");
+        self.verbosity_shift = (index - base_index) as i32;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{self, Read},
+        panic::AssertUnwindSafe,
+        sync::{mpsc, Arc, Mutex},
+        time::Duration,
+    };
+
+    use gag::BufferRedirect;
+
+    use crate::{
+        formatters::Plaintext,
+        test_utils::{lock_console, FakeClock},
+        traits::MockWrite,
+        writers::CaptureWriter,
+        HasSourceLocation, HasTimestamp, Message, Severity,
+    };
+
+    use super::*;
+
+    // fn add_mock_writer<'writers>(logger: &'writers mut Logger<Severity, Message<Severity>>) {
+    //     let mut writer = MockWrite::<Severity, Message<Severity>>::new();
+
+    //     writer.expect_write().times(9).returning(|_| Ok(()));
+
+    //     logger.add_writer(&mut writer);
+    // }
+
+    fn test_logger(logger: &Logger<Severity, Message<Severity>>) {
+        logger.log_message(Message::from_core_fields(Severity::Info, "test"));
+        logger.log_with_severity(Severity::Info, "test");
+        logger.log_trace("test");
+        logger.log_debug("test");
+        logger.log_developer_warning("test");
+        logger.log_info("test");
+        logger.log_warning("test");
+        logger.log_error("test");
+        logger.log_fatal("test");
+    }
+
+    #[test]
+    fn no_op_smoke() {
+        let logger = Logger::<Severity, Message<Severity>>::default();
+
+        test_logger(&logger);
+    }
+
+    #[test]
+    fn debug_output() {
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+        assert_eq!(
+            format!("{:?}", logger),
+            "Logger { min_severity: Trace, writer_count: 0, paused: false }"
+        );
+
+        logger.add_writer(MockWrite::<Severity, Message<Severity>>::new());
+
+        assert_eq!(
+            format!("{:?}", logger),
+            "Logger { min_severity: Trace, writer_count: 1, paused: false }"
+        );
+    }
+
+    #[test]
+    fn writes_messages_local() {
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+        let mut writer = MockWrite::<Severity, Message<Severity>>::new();
+
+        writer.expect_write().times(9).returning(|_| Ok(()));
+
+        logger.add_writer(writer);
+
+        test_logger(&logger);
+    }
+
+    #[test]
+    fn set_min_severity() {
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+        logger.set_min_severity(Severity::Warning);
+
+        assert_eq!(logger.min_severity(), &Severity::Warning);
+        assert!(!logger.is_enabled(&Severity::Info));
+        assert!(logger.is_enabled(&Severity::Warning));
+    }
+
+    #[test]
+    fn writer_min_severity() {
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+        let debug_writer = Arc::new(Mutex::new(CaptureWriter::new()));
+        let alert_writer = Arc::new(Mutex::new(CaptureWriter::new()));
+        let error_writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+        logger.add_writer_shared(debug_writer.clone());
+        logger.add_writer_shared_with_min_severity(alert_writer.clone(), Severity::Warning);
+        logger.add_writer_shared_with_min_severity(error_writer.clone(), Severity::Error);
+        logger.set_min_severity(Severity::Debug);
+
+        logger.log_trace("dropped by logger");
+        logger.log_info("verbose");
+        logger.log_warning("alert");
+
+        assert_eq!(debug_writer.lock().unwrap().message_count(), 2);
+        assert_eq!(alert_writer.lock().unwrap().message_count(), 1);
+        assert_eq!(
+            alert_writer.lock().unwrap().last_message().unwrap().text(),
+            "alert"
+        );
+        assert_eq!(error_writer.lock().unwrap().message_count(), 0);
+
+        // Broadcast messages ignore every minimum severity
+        logger.broadcast(Message::from_core_fields(Severity::Trace, "starting up"));
+
+        assert_eq!(alert_writer.lock().unwrap().message_count(), 2);
+        assert_eq!(error_writer.lock().unwrap().message_count(), 1);
+    }
+
+    #[test]
+    fn capture_writer() {
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+        let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+        logger.add_writer_shared(writer.clone());
+
+        logger.log_info("connected");
+        logger.log_warning("slow response");
+        logger.log_error("disconnected");
+
+        let writer = writer.lock().unwrap();
+        let messages = writer.messages();
+
+        assert_eq!(messages.len(), 3);
+        assert_eq!(*messages[1].severity(), Severity::Warning);
+        assert_eq!(messages[2].text(), "disconnected");
+    }
+
+    #[test]
+    fn set_min_severity_filters_messages() {
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+        let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+        logger.add_writer_shared(writer.clone());
+
+        logger.log_debug("before");
+        logger.set_min_severity(Severity::Warning);
+        logger.log_debug("suppressed");
+        logger.log_info("suppressed");
+        logger.log_warning("at threshold");
+        logger.log_error("above threshold");
+
+        let texts = writer
+            .lock()
+            .unwrap()
+            .messages()
+            .iter()
+            .map(|message| message.text().to_owned())
+            .collect::<Vec<_>>();
+
+        assert_eq!(texts, ["before", "at threshold", "above threshold"]);
+    }
+
+    #[test]
+    fn set_verbosity_delta() {
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+        logger.set_min_severity(Severity::Info);
+
+        logger.set_verbosity_delta(-1);
+        assert_eq!(logger.min_severity(), &Severity::DeveloperWarning);
+
+        logger.set_verbosity_delta(1);
+        assert_eq!(logger.min_severity(), &Severity::Warning);
+
+        logger.set_verbosity_delta(0);
+        assert_eq!(logger.min_severity(), &Severity::Info);
+
+        // Clamped deltas do not lose track of the base
+        logger.set_verbosity_delta(-10);
+        assert_eq!(logger.min_severity(), &Severity::Trace);
+
+        logger.set_verbosity_delta(10);
+        assert_eq!(logger.min_severity(), &Severity::Fatal);
+
+        logger.set_verbosity_delta(-1);
+        assert_eq!(logger.min_severity(), &Severity::DeveloperWarning);
+
+        // Setting the minimum severity clears the delta
+        logger.set_min_severity(Severity::Error);
+        logger.set_verbosity_delta(-1);
+        assert_eq!(logger.min_severity(), &Severity::Warning);
+    }
+
+    #[test]
+    fn broadcast() {
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+        let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+        logger.add_writer_shared(writer.clone());
+        logger.set_min_severity(Severity::Fatal);
+        logger.set_global_rate_limit(0.0);
+        logger.pause();
+
+        logger.log_info("dropped");
+        logger.broadcast(Message::from_core_fields(Severity::Info, "starting up"));
+        logger.broadcast(Message::from_core_fields(Severity::Info, "shutting down"));
+
+        assert_eq!(writer.lock().unwrap().message_count(), 2);
+    }
+
+    #[test]
+    fn log_if_enabled_skips_disabled() {
+        let mut logger = Logger::<Severity, Message<Severity>> {
+            min_severity: Severity::Info,
+            ..Default::default()
+        };
+
+        let mut writer = MockWrite::<Severity, Message<Severity>>::new();
+
+        writer.expect_write().times(1).returning(|_| Ok(()));
+
+        logger.add_writer(writer);
+
+        assert!(!logger.is_enabled(&Severity::Debug));
+        assert!(logger.is_enabled(&Severity::Info));
+
+        logger.log_if_enabled(&Severity::Debug, || {
+            panic!("message should not be constructed when disabled")
+        });
+        logger.log_if_enabled(&Severity::Info, || {
+            Message::from_core_fields(Severity::Info, "test")
+        });
+    }
+
+    #[test]
+    fn pause() {
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+        let mut writer = MockWrite::<Severity, Message<Severity>>::new();
+
+        writer.expect_write().times(1).returning(|_| Ok(()));
+
+        logger.add_writer(writer);
+
+        logger.pause();
+
+        assert!(logger.is_paused());
+        assert!(!logger.is_enabled(&Severity::Fatal));
+
+        logger.log_info("test");
+
+        logger.resume();
+
+        assert!(!logger.is_paused());
+
+        logger.log_info("test");
+    }
+
+    #[test]
+    fn snapshot() {
+        let mut logger = Logger::<Severity, Message<Severity>> {
+            min_severity: Severity::Info,
+            ..Default::default()
+        };
+
+        logger.add_writer(MockWrite::<Severity, Message<Severity>>::new());
+        logger.pause();
+
+        assert_eq!(
+            logger.snapshot(),
+            LoggerSnapshot {
+                min_severity: Severity::Info,
+                writer_count: 1,
+                paused: true,
+            }
+        );
+    }
+
+    #[test]
+    fn add_middleware() {
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+        let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+        logger.add_writer_shared(writer.clone());
+
+        logger.add_middleware(|message: Message<Severity>| {
+            if message.text() == "drop" {
+                None
+            } else {
+                Some(message)
+            }
+        });
+        logger.add_middleware(|mut message: Message<Severity>| {
+            message
+                .fields_mut()
+                .push(("processed".to_owned(), "true".to_owned()));
+            Some(message)
+        });
+
+        logger.log_info("drop");
+        logger.log_info("keep");
+
+        let writer = writer.lock().unwrap();
+
+        assert_eq!(writer.message_count(), 1);
+        assert_eq!(writer.last_message().unwrap().text(), "keep");
+        assert_eq!(
+            writer.last_message().unwrap().fields(),
+            [("processed".to_owned(), "true".to_owned())]
+        );
+    }
+
+    #[test]
+    fn log_dyn() {
+        /// A message type other than the logger's.
+        struct OtherMessage;
+
+        impl HasSeverity<Severity> for OtherMessage {
+            fn severity(&self) -> &Severity {
+                &Severity::Warning
+            }
+        }
+
+        impl HasText for OtherMessage {
+            fn text(&self) -> &str {
+                "other"
+            }
+        }
+
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+        let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+        logger.add_writer_shared(writer.clone());
+
+        logger.log_dyn(&OtherMessage);
+
+        let message = writer.lock().unwrap().last_message().unwrap();
+
+        assert_eq!(message.severity(), &Severity::Warning);
+        assert_eq!(message.text(), "other");
+    }
+
+    #[test]
+    fn set_global_rate_limit() {
+        let mut logger =
+            Logger::<Severity, Message<Severity>>::default().with_clock(FakeClock::default());
+
+        let mut writer = MockWrite::<Severity, Message<Severity>>::new();
+
+        writer.expect_write().times(2).returning(|_| Ok(()));
+
+        logger.add_writer(writer);
+
+        assert_eq!(logger.rate_limited_message_count(), 0);
+
+        logger.set_global_rate_limit(2.0);
+
+        logger.log_info("test");
+        logger.log_info("test");
+        logger.log_info("test");
+
+        assert_eq!(logger.rate_limited_message_count(), 1);
+    }
+
+    #[test]
+    fn drain() {
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+        let mut writer = MockWrite::<Severity, Message<Severity>>::new();
+
+        writer.expect_flush().times(1).returning(|| Ok(()));
+
+        logger.add_writer(writer);
+
+        logger.drain();
+    }
+
+    #[test]
+    fn created_at() {
+        let before = Instant::now();
+
+        let logger = Logger::<Severity, Message<Severity>>::default();
+
+        assert!(logger.created_at() >= before);
+        assert!(logger.created_at() <= Instant::now());
+    }
+
+    #[test]
+    fn with_prefix() {
+        let mut logger = Logger::<Severity, Message<Severity>>::default().with_prefix("[mylib] ");
+
+        let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+        logger.add_writer_shared(writer.clone());
+
+        logger.log_info("hello, world");
+
+        assert_eq!(
+            writer.lock().unwrap().last_message().unwrap().text(),
+            "[mylib] hello, world"
+        );
+    }
+
+    #[test]
+    fn remove_writer() {
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+        let mut writer = MockWrite::<Severity, Message<Severity>>::new();
+
+        writer.expect_write().times(1).returning(|_| Ok(()));
+
+        let handle = logger.add_writer(writer);
+
+        assert!(handle.is_alive(&logger));
+
+        logger.log_info("test");
+
+        assert!(logger.remove_writer(handle));
+        assert!(!handle.is_alive(&logger));
+        assert!(!logger.remove_writer(handle));
+
+        logger.log_info("test");
+    }
+
+    #[test]
+    fn clear_writers() {
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+        let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+        logger.add_writer_shared(writer.clone());
+        logger.add_split_writer(Plaintext::new_default(), Arc::new(Mutex::new(io::sink())));
+
+        assert_eq!(logger.writer_count(), 2);
+
+        logger.log_info("before");
+        logger.clear_writers();
+        logger.log_info("after");
+
+        assert_eq!(logger.writer_count(), 0);
+        assert_eq!(writer.lock().unwrap().messages().len(), 1);
+        assert_eq!(writer.lock().unwrap().messages()[0].text(), "before");
+    }
+
+    #[test]
+    fn writers() {
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+        logger.add_writer(CaptureWriter::new());
+
+        let dropped_writer: Arc<Mutex<dyn Write<Severity, Message<Severity>> + Send>> =
+            Arc::new(Mutex::new(CaptureWriter::new()));
+
+        logger.add_writer_weak(Arc::downgrade(&dropped_writer));
+        logger.add_split_writer(Plaintext::new_default(), Arc::new(Mutex::new(io::sink())));
+
+        assert_eq!(
+            logger.writers().collect::<Vec<_>>(),
+            ["capture", "capture", "split"]
+        );
+
+        drop(dropped_writer);
+
+        assert_eq!(logger.writers().collect::<Vec<_>>(), ["capture", "split"]);
+    }
+
+    #[test]
+    fn find_writer_by_name() -> Result<()> {
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+        let first_writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+        logger.add_split_writer(Plaintext::new_default(), Arc::new(Mutex::new(io::sink())));
+        logger.add_writer_shared(first_writer.clone());
+        logger.add_writer(CaptureWriter::new());
+
+        assert!(logger.find_writer_by_name("split").is_none());
+        assert!(logger.find_writer_by_name("console").is_none());
+
+        logger
+            .find_writer_by_name("capture")
+            .expect("capture writer was added")
+            .lock()
+            .unwrap()
+            .write(&Message::from_core_fields(Severity::Info, "hello"))?;
+
+        assert_eq!(first_writer.lock().unwrap().message_count(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn log_message_macro_source_location() {
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+        let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+        logger.add_writer_shared(writer.clone());
+
+        crate::log_message!(logger = logger, severity = Severity::Info, text = "hello");
+        let line = line!() - 1;
+
+        let message = writer.lock().unwrap().last_message().unwrap();
+
+        assert_eq!(message.source_file(), Some(file!()));
+        assert_eq!(message.source_line(), Some(line));
+        assert_eq!(message.source_module(), Some(module_path!()));
+    }
+
+    #[test]
+    fn log_message_macro_timestamp() {
+        let clock = FakeClock::at(SystemTime::UNIX_EPOCH + Duration::from_secs(60));
+
+        let mut logger = Logger::<Severity, Message<Severity>>::default().with_clock(clock.clone());
+
+        let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+        logger.add_writer_shared(writer.clone());
+
+        crate::log_message!(logger = logger, severity = Severity::Info, text = "hello");
+
+        assert_eq!(
+            writer.lock().unwrap().last_message().unwrap().timestamp(),
+            clock.now()
+        );
+
+        // An explicit timestamp takes precedence over the logger's clock
+        crate::log_message!(
+            logger = logger,
+            severity = Severity::Info,
+            text = "hello",
+            timestamp = SystemTime::UNIX_EPOCH
+        );
+
+        assert_eq!(
+            writer.lock().unwrap().last_message().unwrap().timestamp(),
+            SystemTime::UNIX_EPOCH
+        );
+    }
+
+    #[test]
+    fn log_methods_timestamp() {
+        let clock = FakeClock::at(SystemTime::UNIX_EPOCH + Duration::from_secs(60));
+
+        let mut logger = Logger::<Severity, Message<Severity>>::default().with_clock(clock.clone());
+
+        let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+        logger.add_writer_shared(writer.clone());
+
+        logger.log_info("hello");
+        logger.log_with_fields(Severity::Info, "hello", Vec::new());
+        logger.log_structured(Severity::Info, [("event", "login")]);
+
+        let messages = writer.lock().unwrap().messages();
+
+        assert_eq!(messages.len(), 3);
+
+        for message in messages {
+            assert_eq!(message.timestamp(), clock.now());
+        }
     }
 
-    /// Logs a developer warning message
-    ///
-    /// # Arguments
-    ///
-    /// * `text` - The text content of the message
-    pub fn log_developer_warning(&self, text: &str)
-    where
-        Message: FromCoreFields<Severity>,
-        Severity: HasDeveloperWarningSeverity,
-    {
-        self.log_with_severity(Severity::developer_warning_severity(), text);
+    #[test]
+    fn with_clock() {
+        let logger =
+            Logger::<Severity, Message<Severity>>::default().with_clock(FakeClock::default());
+
+        assert_eq!(logger.now(), SystemTime::UNIX_EPOCH);
     }
 
-    /// Logs an info message
-    ///
-    /// # Arguments
-    ///
-    /// * `text` - The text content of the message
-    pub fn log_info(&self, text: &str)
-    where
-        Message: FromCoreFields<Severity>,
-        Severity: HasInfoSeverity,
-    {
-        self.log_with_severity(Severity::info_severity(), text);
+    #[test]
+    fn log_with_fields() {
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+        let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+        logger.add_writer_shared(writer.clone());
+
+        logger.log_with_fields(
+            Severity::Warning,
+            "slow request",
+            vec![("duration_ms".to_owned(), "1500".to_owned())],
+        );
+
+        let message = writer.lock().unwrap().last_message().unwrap();
+
+        assert_eq!(*message.severity(), Severity::Warning);
+        assert_eq!(message.text(), "slow request");
+        assert_eq!(
+            message.fields(),
+            [("duration_ms".to_owned(), "1500".to_owned())]
+        );
     }
 
-    /// Logs a warning message
-    ///
-    /// # Arguments
-    ///
-    /// * `text` - The text content of the message
-    pub fn log_warning(&self, text: &str)
-    where
-        Message: FromCoreFields<Severity>,
-        Severity: HasWarningSeverity,
-    {
-        self.log_with_severity(Severity::warning_severity(), text);
+    #[test]
+    fn log_structured() {
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+        let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+        logger.add_writer_shared(writer.clone());
+
+        logger.log_structured(Severity::Info, [("event", "login"), ("user_id", "42")]);
+
+        let message = writer.lock().unwrap().last_message().unwrap();
+
+        assert_eq!(*message.severity(), Severity::Info);
+        assert_eq!(message.text(), "");
+        assert_eq!(
+            message.fields(),
+            [
+                ("event".to_owned(), "login".to_owned()),
+                ("user_id".to_owned(), "42".to_owned())
+            ]
+        );
     }
 
-    /// Logs an error message
-    ///
-    /// # Arguments
-    ///
-    /// * `text` - The text content of the message
-    pub fn log_error(&self, text: &str)
-    where
-        Message: FromCoreFields<Severity>,
-        Severity: HasErrorSeverity,
-    {
-        self.log_with_severity(Severity::error_severity(), text);
+    #[test]
+    fn log_structured_error() {
+        #[derive(Debug)]
+        struct TestError {
+            description: &'static str,
+            source: Option<Box<TestError>>,
+        }
+
+        impl fmt::Display for TestError {
+            fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(formatter, "{}", self.description)
+            }
+        }
+
+        impl error::Error for TestError {
+            fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+                self.source
+                    .as_ref()
+                    .map(|source| source.as_ref() as &(dyn error::Error + 'static))
+            }
+        }
+
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+        let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+        logger.add_writer_shared(writer.clone());
+
+        logger.log_structured_error(&TestError {
+            description: "request failed",
+            source: Some(Box::new(TestError {
+                description: "connection reset",
+                source: Some(Box::new(TestError {
+                    description: "broken pipe",
+                    source: None,
+                })),
+            })),
+        });
+
+        let message = writer.lock().unwrap().last_message().unwrap();
+
+        assert_eq!(*message.severity(), Severity::Error);
+        assert_eq!(
+            message.fields(),
+            [
+                ("error".to_owned(), "request failed".to_owned()),
+                ("cause_0".to_owned(), "connection reset".to_owned()),
+                ("cause_1".to_owned(), "broken pipe".to_owned())
+            ]
+        );
     }
 
-    /// Logs a fatal error message
-    ///
-    /// # Arguments
-    ///
-    /// * `text` - The text content of the message
-    pub fn log_fatal(&self, text: &str)
-    where
-        Message: FromCoreFields<Severity>,
-        Severity: HasFatalSeverity,
-    {
-        self.log_with_severity(Severity::fatal_severity(), text);
+    #[test]
+    fn on_message_logged() {
+        let mut logger = Logger::<Severity, Message<Severity>> {
+            min_severity: Severity::Info,
+            ..Default::default()
+        };
+
+        let logged_texts = Arc::new(Mutex::new(Vec::new()));
+        let logged_texts_hook = logged_texts.clone();
+
+        logger.on_message_logged(move |message| {
+            logged_texts_hook
+                .lock()
+                .unwrap()
+                .push(message.text().to_owned());
+        });
+
+        logger.log_debug("filtered");
+        logger.log_info("first");
+        logger.pause();
+        logger.log_error("paused");
+
+        // Hooks are called regardless of filtering
+        assert_eq!(
+            *logged_texts.lock().unwrap(),
+            ["filtered", "first", "paused"]
+        );
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use std::sync::{Arc, Mutex};
+    #[test]
+    fn on_writer_error() {
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
 
-    use crate::{traits::MockWrite, Message, Severity};
+        let mut failing_writer = MockWrite::<Severity, Message<Severity>>::new();
 
-    use super::*;
+        failing_writer
+            .expect_write()
+            .times(2)
+            .returning(|_| Err(io::Error::new(io::ErrorKind::BrokenPipe, "broken pipe").into()));
 
-    // fn add_mock_writer<'writers>(logger: &'writers mut Logger<Severity, Message<Severity>>) {
-    //     let mut writer = MockWrite::<Severity, Message<Severity>>::new();
+        let mut writer = MockWrite::<Severity, Message<Severity>>::new();
 
-    //     writer.expect_write().times(9).returning(|_| Ok(()));
+        writer.expect_write().times(2).returning(|_| Ok(()));
 
-    //     logger.add_writer(&mut writer);
-    // }
+        logger.add_writer(failing_writer);
+        logger.add_writer(writer);
+
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let errors_hook = errors.clone();
+
+        logger.on_writer_error(move |error| {
+            errors_hook.lock().unwrap().push(error.to_string());
+        });
+
+        logger.log_info("first");
+        logger.log_info("second");
+
+        assert_eq!(
+            *errors.lock().unwrap(),
+            [
+                "Failed to write log message: broken pipe",
+                "Failed to write log message: broken pipe"
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to write message")]
+    fn writer_error_panics_without_hook() {
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+        let mut writer = MockWrite::<Severity, Message<Severity>>::new();
+
+        writer
+            .expect_write()
+            .returning(|_| Err(io::Error::new(io::ErrorKind::BrokenPipe, "broken pipe").into()));
+
+        logger.add_writer(writer);
 
-    fn test_logger(logger: &Logger<Severity, Message<Severity>>) {
-        logger.log_message(Message::from_core_fields(Severity::Info, "test"));
-        logger.log_with_severity(Severity::Info, "test");
-        logger.log_trace("test");
-        logger.log_debug("test");
-        logger.log_developer_warning("test");
         logger.log_info("test");
-        logger.log_warning("test");
-        logger.log_error("test");
-        logger.log_fatal("test");
+    }
+
+    /// Logs a message when it is dropped.
+    struct LogOnDrop<'logger>(&'logger Logger<Severity, Message<Severity>>);
+
+    impl Drop for LogOnDrop<'_> {
+        fn drop(&mut self) {
+            self.0.log_info("dropped");
+        }
     }
 
     #[test]
-    fn no_op_smoke() {
-        let logger = Logger::<Severity, Message<Severity>>::default();
+    fn logs_while_unwinding_with_locked_writer() {
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
 
-        test_logger(&logger);
+        let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+        logger.add_writer_shared(writer.clone());
+
+        let (locked_sender, locked_receiver) = mpsc::channel();
+
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                let _guard = writer.lock().unwrap();
+
+                locked_sender.send(()).unwrap();
+
+                thread::sleep(Duration::from_millis(50));
+            });
+
+            locked_receiver.recv().unwrap();
+
+            // The message logged while unwinding waits for the other thread to release the writer
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                let _log_on_drop = LogOnDrop(&logger);
+
+                panic!("unwinding");
+            }));
+
+            assert!(result.is_err());
+        });
+
+        assert_eq!(
+            writer.lock().unwrap().last_message().unwrap().text(),
+            "dropped"
+        );
     }
 
     #[test]
-    fn writes_messages_local() {
+    fn writer_error_while_unwinding_does_not_panic() {
         let mut logger = Logger::<Severity, Message<Severity>>::default();
 
         let mut writer = MockWrite::<Severity, Message<Severity>>::new();
 
-        writer.expect_write().times(9).returning(|_| Ok(()));
+        writer
+            .expect_write()
+            .returning(|_| Err(io::Error::new(io::ErrorKind::BrokenPipe, "broken pipe").into()));
 
         logger.add_writer(writer);
 
-        test_logger(&logger);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let _log_on_drop = LogOnDrop(&logger);
+
+            panic!("unwinding");
+        }));
+
+        // The original panic is still the one that was caught, rather than the process aborting
+        assert_eq!(
+            result.unwrap_err().downcast_ref::<&str>(),
+            Some(&"unwinding")
+        );
+    }
+
+    #[test]
+    fn writer_error_policy_ignore() {
+        let mut logger = Logger::<Severity, Message<Severity>>::default()
+            .with_writer_error_policy(WriterErrorPolicy::Ignore);
+
+        let mut failing_writer = MockWrite::<Severity, Message<Severity>>::new();
+
+        failing_writer
+            .expect_write()
+            .returning(|_| Err(io::Error::new(io::ErrorKind::BrokenPipe, "broken pipe").into()));
+
+        let mut writer = MockWrite::<Severity, Message<Severity>>::new();
+
+        writer.expect_write().times(1).returning(|_| Ok(()));
+
+        logger.add_writer(failing_writer);
+        logger.add_writer(writer);
+
+        logger.log_info("test");
+    }
+
+    #[test]
+    fn writer_error_policy_log_to_stderr() {
+        let mut logger = Logger::<Severity, Message<Severity>>::default()
+            .with_writer_error_policy(WriterErrorPolicy::LogToStderr);
+
+        let _console_lock = lock_console();
+
+        let mut failing_writer = MockWrite::<Severity, Message<Severity>>::new();
+
+        failing_writer
+            .expect_write()
+            .returning(|_| Err(io::Error::new(io::ErrorKind::BrokenPipe, "broken pipe").into()));
+
+        logger.add_writer(failing_writer);
+
+        let mut buffer_stderr = BufferRedirect::stderr().unwrap();
+
+        logger.log_info("test");
+
+        let mut output_stderr = String::new();
+
+        buffer_stderr.read_to_string(&mut output_stderr).unwrap();
+
+        drop(buffer_stderr);
+
+        assert!(
+            output_stderr.starts_with("Failed to write message: "),
+            "{:?}",
+            output_stderr
+        );
+        assert!(
+            output_stderr.ends_with("broken pipe: [info] test\n"),
+            "{:?}",
+            output_stderr
+        );
+    }
+
+    #[test]
+    fn poisoned_writer() {
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+        let writer: Arc<Mutex<dyn Write<Severity, Message<Severity>> + Send>> =
+            Arc::new(Mutex::new(CaptureWriter::new()));
+
+        let poisoner = writer.clone();
+
+        let _ = std::thread::spawn(move || {
+            let _guard = poisoner.lock().unwrap();
+
+            panic!("poison the writer");
+        })
+        .join();
+
+        logger.add_writer_shared(writer);
+
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let errors_hook = errors.clone();
+
+        logger.on_writer_error(move |error| {
+            errors_hook.lock().unwrap().push(error.to_string());
+        });
+
+        logger.log_info("test");
+
+        assert_eq!(
+            *errors.lock().unwrap(),
+            ["Log writer failed: writer mutex is poisoned"]
+        );
+    }
+
+    #[test]
+    fn add_writer_weak() {
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+        let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+        let writer_dyn: Arc<Mutex<dyn Write<Severity, Message<Severity>> + Send>> = writer.clone();
+
+        logger.add_writer_weak(Arc::downgrade(&writer_dyn));
+
+        logger.log_info("test");
+
+        assert_eq!(writer.lock().unwrap().message_count(), 1);
+
+        drop(writer_dyn);
+        drop(writer);
+
+        // This should not panic even though the writer is gone
+        logger.log_info("test");
+    }
+
+    #[test]
+    fn add_split_writer() {
+        /// A formatter that checks that the output is not locked while formatting.
+        struct UnlockedOutputChecker {
+            output: Arc<Mutex<Vec<u8>>>,
+        }
+
+        impl Format<Severity, Message<Severity>> for UnlockedOutputChecker {
+            fn format(
+                &mut self,
+                message: &Message<Severity>,
+                writer: &mut dyn io::Write,
+            ) -> Result<()> {
+                assert!(self.output.try_lock().is_ok());
+
+                Ok(write!(writer, "{}", message.text())?)
+            }
+        }
+
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+        let output = Arc::new(Mutex::new(Vec::new()));
+
+        let handle = logger.add_split_writer(
+            UnlockedOutputChecker {
+                output: output.clone(),
+            },
+            output.clone(),
+        );
+
+        logger.log_info("first");
+        logger.log_info("second");
+        logger.drain();
+
+        assert_eq!(output.lock().unwrap().as_slice(), b"first\nsecond\n");
+        assert!(logger.remove_writer(handle));
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn with_metrics_integration() {
+        use metrics::{
+            Counter, CounterFn, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString,
+            Unit,
+        };
+
+        /// A recorder that records the keys of the counters that are incremented.
+        #[derive(Default)]
+        struct KeyRecorder {
+            keys: Arc<Mutex<Vec<String>>>,
+        }
+
+        struct KeyCounter {
+            key: String,
+            keys: Arc<Mutex<Vec<String>>>,
+        }
+
+        impl CounterFn for KeyCounter {
+            fn increment(&self, _: u64) {
+                self.keys.lock().unwrap().push(self.key.clone());
+            }
+
+            fn absolute(&self, _: u64) {}
+        }
+
+        impl Recorder for KeyRecorder {
+            fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+            fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+            fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+            fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+                Counter::from_arc(Arc::new(KeyCounter {
+                    key: key.to_string(),
+                    keys: self.keys.clone(),
+                }))
+            }
+
+            fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+                Gauge::noop()
+            }
+
+            fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+                Histogram::noop()
+            }
+        }
+
+        let recorder = KeyRecorder::default();
+
+        let logger = Logger::<Severity, Message<Severity>> {
+            min_severity: Severity::Info,
+            ..Default::default()
+        }
+        .with_metrics_integration();
+
+        metrics::with_local_recorder(&recorder, || {
+            logger.log_debug("test");
+            logger.log_info("test");
+            logger.log_error("test");
+        });
+
+        let keys = recorder.keys.lock().unwrap();
+
+        assert_eq!(keys.len(), 2);
+        assert!(keys[0].contains("log_messages_total") && keys[0].contains("info"));
+        assert!(keys[1].contains("log_messages_total") && keys[1].contains("error"));
     }
 
     #[test]
@@ -317,8 +2645,13 @@ mod tests {
             .times(9)
             .returning(|_| Ok(()));
 
-        Logger::<Severity, Message<Severity>>::global().add_writer_shared(writer);
+        // Holding the lock keeps other tests from logging to the global logger in the meantime
+        let mut logger = Logger::<Severity, Message<Severity>>::global_mut();
+
+        let handle = logger.add_writer_shared(writer);
+
+        test_logger(&logger);
 
-        test_logger(Logger::<Severity, Message<Severity>>::global());
+        logger.remove_writer(handle);
     }
 }
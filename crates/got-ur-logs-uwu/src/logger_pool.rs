@@ -0,0 +1,196 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    sync::{Arc, Mutex},
+    thread,
+    time::SystemTime,
+};
+
+use crate::{HasSeverity, HasText, IsSeverity, Logger, Write};
+
+/// A pool of loggers that spreads logging from many threads across several independent loggers.
+///
+/// Each logger in the pool is called a shard and has its own writers. Messages are logged to the
+/// shard picked by hashing the ID of the current thread, so a thread always logs to the same shard
+/// while different threads are spread out, which reduces contention on the writers' locks.
+/// Writers that should receive every message can be added to all the shards at once with
+/// [`LoggerPool::add_shared_writer`].
+///
+/// # Example
+///
+/// ```
+/// # use got_ur_logs_uwu::{
+/// #     writers::ConsoleWriter, LoggerPool, Message, Severity, formatters::Plaintext
+/// # };
+/// #
+/// let mut pool = LoggerPool::<Severity, Message<Severity>>::new(4);
+///
+/// // Give each shard its own writer
+/// for shard in pool.shards_mut() {
+///     shard.add_writer(ConsoleWriter::new_stdout(Plaintext::new_default()));
+/// }
+///
+/// pool.shard().log_info("hello, world");
+/// ```
+pub struct LoggerPool<Severity: IsSeverity, Message: HasSeverity<Severity> + HasText> {
+    shards: Vec<Logger<Severity, Message>>,
+}
+
+impl<Severity: IsSeverity, Message: HasSeverity<Severity> + HasText> LoggerPool<Severity, Message> {
+    /// Create a new pool of default loggers.
+    ///
+    /// # Arguments
+    ///
+    /// * `shard_count` - The number of loggers in the pool, which must not be zero
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shard_count` is zero.
+    pub fn new(shard_count: usize) -> Self {
+        assert!(shard_count > 0, "logger pool must have at least one shard");
+
+        Self {
+            shards: (0..shard_count).map(|_| Logger::default()).collect(),
+        }
+    }
+
+    /// Gets the number of loggers in the pool.
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Gets the logger that the current thread logs to.
+    pub fn shard(&self) -> &Logger<Severity, Message> {
+        let mut hasher = DefaultHasher::new();
+
+        thread::current().id().hash(&mut hasher);
+
+        &self.shards[(hasher.finish() % self.shards.len() as u64) as usize]
+    }
+
+    /// Gets mutable access to all the loggers in the pool so that they can be configured.
+    pub fn shards_mut(&mut self) -> impl Iterator<Item = &mut Logger<Severity, Message>> {
+        self.shards.iter_mut()
+    }
+
+    /// Adds a shared writer instance to every logger in the pool.
+    ///
+    /// The writer receives the messages logged by every thread, so its lock is shared between
+    /// all the shards.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::sync::{Arc, Mutex};
+    /// # use got_ur_logs_uwu::{
+    /// #     writers::ConsoleWriter, LoggerPool, Message, Severity, formatters::Plaintext
+    /// # };
+    /// #
+    /// # let mut pool = LoggerPool::<Severity, Message<Severity>>::new(4);
+    /// #
+    /// pool.add_shared_writer(Arc::new(Mutex::new(
+    ///     ConsoleWriter::new_stderr(
+    ///         Plaintext::new_default()
+    ///     )
+    /// )));
+    /// ```
+    pub fn add_shared_writer(&mut self, writer: Arc<Mutex<dyn Write<Severity, Message> + Send>>) {
+        for shard in &mut self.shards {
+            shard.add_writer_shared(writer.clone());
+        }
+    }
+
+    /// Gets the current time according to the clock of the current thread's logger.
+    pub fn now(&self) -> SystemTime {
+        self.shard().now()
+    }
+
+    /// Logs a message to the logger of the current thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message to log
+    pub fn log_message(&self, message: Message) {
+        self.shard().log_message(message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr;
+
+    use crate::{writers::CaptureWriter, FromCoreFields, Message, Severity};
+
+    use super::*;
+
+    #[test]
+    fn same_shard_per_thread() {
+        let pool = LoggerPool::<Severity, Message<Severity>>::new(8);
+
+        assert_eq!(pool.shard_count(), 8);
+        assert!(ptr::eq(pool.shard(), pool.shard()));
+    }
+
+    #[test]
+    fn shards_have_own_writers() {
+        let mut pool = LoggerPool::<Severity, Message<Severity>>::new(2);
+
+        let writers: Vec<_> = pool
+            .shards_mut()
+            .map(|shard| {
+                let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+                shard.add_writer_shared(writer.clone());
+
+                writer
+            })
+            .collect();
+
+        pool.log_message(Message::from_core_fields(Severity::Info, "test"));
+
+        let counts: Vec<_> = writers
+            .iter()
+            .map(|writer| writer.lock().unwrap().message_count())
+            .collect();
+
+        assert_eq!(counts.iter().sum::<usize>(), 1);
+    }
+
+    #[test]
+    fn shared_writer_receives_all_messages() {
+        let mut pool = LoggerPool::<Severity, Message<Severity>>::new(4);
+
+        let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+        pool.add_shared_writer(writer.clone());
+
+        thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| pool.shard().log_info("test"));
+            }
+        });
+
+        assert_eq!(writer.lock().unwrap().message_count(), 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "logger pool must have at least one shard")]
+    fn no_shards() {
+        LoggerPool::<Severity, Message<Severity>>::new(0);
+    }
+}
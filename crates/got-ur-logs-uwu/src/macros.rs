@@ -16,39 +16,129 @@
 #[allow(unused_imports)]
 use crate::private::MessageBuilder; // Used by doc comment
 
-/// Logs a message to the default global logger.
+/// Logs a message to the default global logger, or to a given logger.
 ///
 /// # Arguments
 ///
 /// Takes a comma-separated list of key-value pairs. The allowed keys are:
+/// * `logger` - The logger to log to instead of the global logger (optional, must come first)
 /// * `severity` - The severity of the message
 /// * `text` - The text content of the message
+/// * `tags` - Tags used to categorize the message (optional)
+/// * `correlation_id` - An ID used to associate related messages (optional)
+/// * `fields` - Structured key-value pairs (optional)
+/// * `timestamp` - When the message was emitted, which is the current time according to the
+///   logger's clock by default (optional)
+/// * `source_file`, `source_line`, `source_module` - Where the message was logged from, which is
+///   the location of the macro call by default (optional)
 ///
 /// # Example
 ///
 /// ```
+/// # use std::time::SystemTime;
 /// # use got_ur_logs_uwu::{
+/// #     Logger,
+/// #     Message,
 /// #     Severity,
 /// #     log_message,
 /// # };
 /// #
 /// log_message!(severity = Severity::Info, text = "hello, world");
+/// log_message!(severity = Severity::Info, text = "hello, world", tags = vec!["http", "slow"]);
+/// log_message!(severity = Severity::Info, text = "hello, world", correlation_id = "request-1");
+/// log_message!(severity = Severity::Info, text = "hello, world", fields = vec![("user_id", "42")]);
+/// log_message!(severity = Severity::Info, text = "hello, world", timestamp = SystemTime::UNIX_EPOCH);
+///
+/// // Log to a specific logger instead of the global one
+/// let logger = Logger::<Severity, Message<Severity>>::default();
+///
+/// log_message!(logger = logger, severity = Severity::Info, text = "hello, world");
+/// ```
+///
+/// Unknown keys are reported at compile time:
+///
+/// ```compile_fail
+/// # use got_ur_logs_uwu::{
+/// #     Severity,
+/// #     log_message,
+/// # };
+/// #
+/// log_message!(severity = Severity::Info, text = "hello, world", typo = "oops");
 /// ```
 #[macro_export]
 macro_rules! log_message {
-    ($($field:ident = $value:expr),* $(,)?) => {
-        $crate::Logger::global().log_message(
+    (logger = $logger:expr $(, $field:ident = $value:expr)* $(,)?) => {{
+        $(
+            $crate::__log_message_check_field!($field);
+        )*
+
+        let logger = &$logger;
+
+        logger.log_message($crate::__build_message!(now = logger.now() $(, $field = $value)*))
+    }};
+    ($($field:ident = $value:expr),* $(,)?) => {{
+        $(
+            $crate::__log_message_check_field!($field);
+        )*
+
+        let global = $crate::Logger::global;
+
+        // The global logger is only locked after the fields are evaluated, since they might log
+        // too
+        let message = $crate::__build_message!(now = global().now() $(, $field = $value)*);
+
+        global().log_message(message)
+    }};
+}
+
+/// Builds a message from the key-value pairs given to [`log_message!`].
+///
+/// The timestamp defaults to `now`, which is only evaluated after the fields.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __build_message {
+    (now = $now:expr $(, $field:ident = $value:expr)*) => {
+        $crate::BuildMessage::build(
             #[allow(clippy::needless_update)]
             $crate::private::MessageBuilder {
                 $(
-                    $field: Some($value),
+                    $field: Some($crate::private::IntoField::into_field($value)),
                 )*
-                ..std::default::Default::default()
-            }.build()
+                ..$crate::private::MessageBuilder {
+                    timestamp: Some($now),
+                    ..$crate::private::MessageBuilder::at_source_location(
+                        ::std::file!(),
+                        ::std::line!(),
+                        ::std::module_path!(),
+                    )
+                }
+            }
         )
     };
 }
 
+/// Checks that a key given to [`log_message!`] is a field of [`MessageBuilder`].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log_message_check_field {
+    (severity) => {};
+    (text) => {};
+    (tags) => {};
+    (correlation_id) => {};
+    (fields) => {};
+    (timestamp) => {};
+    (source_file) => {};
+    (source_line) => {};
+    (source_module) => {};
+    ($field:ident) => {
+        compile_error!(concat!(
+            "unknown field '",
+            stringify!($field),
+            "' in log_message!, see the MessageBuilder documentation for the allowed fields"
+        ));
+    };
+}
+
 /// Logs a message with core fields to the default global logger.
 ///
 /// # Arguments
@@ -57,8 +147,13 @@ macro_rules! log_message {
 /// * `severity` - The severity of the message
 /// * `text` - The text content of the message
 ///
-/// Additionally, takes a comma-separated list of key-value pairs. The keys correspond to the fields
-/// of the [`MessageBuilder`] type.
+/// When the text is a string literal it is treated as a format string, like with [`format!`], and
+/// can be followed by format arguments. The text is only formatted if the severity is enabled on
+/// the logger.
+///
+/// Without format arguments, it can be followed by a comma-separated list of key-value pairs. The
+/// keys correspond to the fields of the [`MessageBuilder`] type. Since these look like named format
+/// arguments, use inline arguments like `{name}` in the format string instead.
 ///
 /// # Example
 ///
@@ -69,27 +164,86 @@ macro_rules! log_message {
 /// # };
 /// #
 /// log_with_severity!(Severity::Info, "hello, world");
+/// log_with_severity!(Severity::Info, "connected to {} in {}ms", "localhost", 42);
+///
+/// let user_id = 42;
+///
+/// log_with_severity!(Severity::Info, "user {user_id} logged in", tags = vec!["auth"]);
+/// ```
+///
+/// Format arguments are not evaluated when the severity is disabled:
+///
+/// ```
+/// # use got_ur_logs_uwu::{
+/// #     Logger,
+/// #     Message,
+/// #     Severity,
+/// #     log_with_severity,
+/// # };
+/// #
+/// fn expensive() -> String {
+///     panic!("this should not be called");
+/// }
+///
+/// Logger::<Severity, Message<Severity>>::global_mut().set_min_severity(Severity::Info);
+///
+/// log_with_severity!(Severity::Debug, "state: {}", expensive());
 /// ```
 #[macro_export]
 macro_rules! log_with_severity {
-    ($severity:expr, $text:expr $(, $field:ident = $value:expr)*) => {
+    ($severity:expr, $format:literal $(, $field:ident = $value:expr)* $(,)?) => {
+        $crate::__log_formatted!($severity, ($format) $(, $field = $value)*)
+    };
+    ($severity:expr, $format:literal, $($argument:tt)+) => {
+        $crate::__log_formatted!($severity, ($format, $($argument)+))
+    };
+    ($severity:expr, $text:expr $(, $field:ident = $value:expr)* $(,)?) => {
         $crate::log_message!(
             severity = $severity,
-            text = $text,
+            text = $text
             $(, $field = $value)*
         )
     };
 }
 
+/// Logs a message to the default global logger, only formatting its text if the severity is
+/// enabled.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log_formatted {
+    ($severity:expr, ($($format:tt)+) $(, $field:ident = $value:expr)*) => {{
+        let severity = $severity;
+        let global = $crate::Logger::global;
+
+        // The global logger is not kept locked while the text is formatted, since the format
+        // arguments might log too
+        if global().is_enabled(&severity) {
+            $(
+                $crate::__log_message_check_field!($field);
+            )*
+
+            let message = $crate::__build_message!(
+                now = global().now(),
+                severity = severity,
+                text = ::std::format!($($format)+)
+                $(, $field = $value)*
+            );
+
+            global().log_message(message)
+        }
+    }};
+}
+
 /// Logs a trace message to the default global logger.
 ///
 /// # Arguments
 ///
 /// Takes one positional argument:
-/// * `text` - The text content of the message
+/// * `text` - The text content of the message, which can be a format string followed by format
+///   arguments
 ///
 /// Additionally, takes a comma-separated list of key-value pairs. The keys correspond to the fields
-/// of the [`MessageBuilder`] type.
+/// of the [`MessageBuilder`] type. See [`log_with_severity!`] for details.
 ///
 /// # Example
 ///
@@ -103,24 +257,59 @@ macro_rules! log_with_severity {
 /// ```
 #[macro_export]
 macro_rules! log_trace {
-    ($text:expr $(, $field:ident = $value:expr)*) => {
+    ($($argument:tt)+) => {
         $crate::log_with_severity!(
             $crate::Severity::Trace,
-            $text
-            $(, $field = $value)*
+            $($argument)+
         )
     };
 }
 
+/// Logs a lazily-constructed trace message to the default global logger.
+///
+/// The text is only constructed if trace messages are enabled on the logger.
+///
+/// # Arguments
+///
+/// Takes one positional argument:
+/// * `text_fn` - A closure that returns the text content of the message
+///
+/// # Example
+///
+/// ```
+/// # use got_ur_logs_uwu::{
+/// #     Severity,
+/// #     log_trace_lazy,
+/// # };
+/// #
+/// log_trace_lazy!(|| format!("hello, {}", "world"));
+/// ```
+#[macro_export]
+macro_rules! log_trace_lazy {
+    ($text_fn:expr) => {{
+        let logger =
+            $crate::Logger::<$crate::Severity, $crate::Message<$crate::Severity>>::global();
+
+        logger.log_if_enabled(&$crate::Severity::Trace, || {
+            $crate::FromCoreFields::from_core_fields_at(
+                $crate::Severity::Trace,
+                ::std::convert::AsRef::<str>::as_ref(&($text_fn)()),
+                logger.now(),
+            )
+        })
+    }};
+}
+
 /// Logs a debug message to the default global logger.
 ///
 /// # Arguments
 ///
 /// Takes one positional argument:
-/// * `text` - The text content of the message
+/// * `text` - The text content of the message, which can be a format string followed by format
+///   arguments
 ///
 /// Additionally, takes a comma-separated list of key-value pairs. The keys correspond to the fields
-/// of the [`MessageBuilder`] type.
+/// of the [`MessageBuilder`] type. See [`log_with_severity!`] for details.
 ///
 /// # Example
 ///
@@ -134,24 +323,61 @@ macro_rules! log_trace {
 /// ```
 #[macro_export]
 macro_rules! log_debug {
-    ($text:expr $(, $field:ident = $value:expr)*) => {
+    ($($argument:tt)+) => {
         $crate::log_with_severity!(
             $crate::Severity::Debug,
-            $text
-            $(, $field = $value)*
+            $($argument)+
         )
     };
 }
 
+/// Logs a lazily-constructed debug message to the default global logger.
+///
+/// The text is only constructed if debug messages are enabled on the logger.
+///
+/// # Arguments
+///
+/// Takes one positional argument:
+/// * `text_fn` - A closure that returns the text content of the message
+///
+/// # Example
+///
+/// ```
+/// # use got_ur_logs_uwu::{
+/// #     Severity,
+/// #     log_debug_lazy,
+/// # };
+/// #
+/// log_debug_lazy!(|| format!("hello, {}", "world"));
+/// ```
+#[macro_export]
+macro_rules! log_debug_lazy {
+    ($text_fn:expr) => {{
+        let logger =
+            $crate::Logger::<$crate::Severity, $crate::Message<$crate::Severity>>::global();
+
+        logger.log_if_enabled(&$crate::Severity::Debug, || {
+            $crate::FromCoreFields::from_core_fields_at(
+                $crate::Severity::Debug,
+                ::std::convert::AsRef::<str>::as_ref(&($text_fn)()),
+                logger.now(),
+            )
+        })
+    }};
+}
+
 /// Logs a developer warning message to the default global logger.
 ///
+/// When the `dev-warnings` feature is disabled the message is logged as a debug message instead.
+///
 /// # Arguments
 ///
 /// Takes one positional argument:
-/// * `text` - The text content of the message
+/// * `text` - The text content of the message, which can be a format string followed by format
+///   arguments
 ///
 /// Additionally, takes a comma-separated list of key-value pairs. The keys correspond to the fields
-/// of the [`MessageBuilder`] type.
+/// of the [`MessageBuilder`] type. See [`log_with_severity!`] for details.
 ///
 /// # Example
 ///
@@ -165,24 +391,52 @@ macro_rules! log_debug {
 /// ```
 #[macro_export]
 macro_rules! log_developer_warning {
-    ($text:expr $(, $field:ident = $value:expr)*) => {
+    ($($argument:tt)+) => {
         $crate::log_with_severity!(
-            $crate::Severity::DeveloperWarning,
-            $text
-            $(, $field = $value)*
+            <$crate::Severity as $crate::HasDeveloperWarningSeverity>::developer_warning_severity(),
+            $($argument)+
         )
     };
 }
 
+/// Emits a developer warning as a compiler warning at the call site.
+///
+/// Unlike [`log_developer_warning!`], nothing is logged at runtime. This is useful for marking spots
+/// that need to be fixed before release, since the warning shows up every time the code is built.
+///
+/// # Arguments
+///
+/// Takes one positional argument:
+/// * `text` - The text content of the warning, which must be a string literal
+///
+/// # Example
+///
+/// ```
+/// # use got_ur_logs_uwu::compile_log_developer_warning;
+/// #
+/// compile_log_developer_warning!("TODO: fix before release");
+/// ```
+#[macro_export]
+macro_rules! compile_log_developer_warning {
+    ($text:literal) => {{
+        // Calling a deprecated function is the only stable way to emit a custom compiler warning
+        #[deprecated(note = $text)]
+        fn developer_warning() {}
+
+        developer_warning();
+    }};
+}
+
 /// Logs an info message to the default global logger.
 ///
 /// # Arguments
 ///
 /// Takes one positional argument:
-/// * `text` - The text content of the message
+/// * `text` - The text content of the message, which can be a format string followed by format
+///   arguments
 ///
 /// Additionally, takes a comma-separated list of key-value pairs. The keys correspond to the fields
-/// of the [`MessageBuilder`] type.
+/// of the [`MessageBuilder`] type. See [`log_with_severity!`] for details.
 ///
 /// # Example
 ///
@@ -193,14 +447,15 @@ macro_rules! log_developer_warning {
 /// # };
 /// #
 /// log_info!("hello, world");
+/// log_info!("hello, world", tags = vec!["http"]);
+/// log_info!("connected to {} in {}ms", "localhost", 42);
 /// ```
 #[macro_export]
 macro_rules! log_info {
-    ($text:expr $(, $field:ident = $value:expr)*) => {
+    ($($argument:tt)+) => {
         $crate::log_with_severity!(
             $crate::Severity::Info,
-            $text
-            $(, $field = $value)*
+            $($argument)+
         )
     };
 }
@@ -210,10 +465,11 @@ macro_rules! log_info {
 /// # Arguments
 ///
 /// Takes one positional argument:
-/// * `text` - The text content of the message
+/// * `text` - The text content of the message, which can be a format string followed by format
+///   arguments
 ///
 /// Additionally, takes a comma-separated list of key-value pairs. The keys correspond to the fields
-/// of the [`MessageBuilder`] type.
+/// of the [`MessageBuilder`] type. See [`log_with_severity!`] for details.
 ///
 /// # Example
 ///
@@ -227,11 +483,10 @@ macro_rules! log_info {
 /// ```
 #[macro_export]
 macro_rules! log_warning {
-    ($text:expr $(, $field:ident = $value:expr)*) => {
+    ($($argument:tt)+) => {
         $crate::log_with_severity!(
             $crate::Severity::Warning,
-            $text
-            $(, $field = $value)*
+            $($argument)+
         )
     };
 }
@@ -241,10 +496,11 @@ macro_rules! log_warning {
 /// # Arguments
 ///
 /// Takes one positional argument:
-/// * `text` - The text content of the message
+/// * `text` - The text content of the message, which can be a format string followed by format
+///   arguments
 ///
 /// Additionally, takes a comma-separated list of key-value pairs. The keys correspond to the fields
-/// of the [`MessageBuilder`] type.
+/// of the [`MessageBuilder`] type. See [`log_with_severity!`] for details.
 ///
 /// # Example
 ///
@@ -258,11 +514,10 @@ macro_rules! log_warning {
 /// ```
 #[macro_export]
 macro_rules! log_error {
-    ($text:expr $(, $field:ident = $value:expr)*) => {
+    ($($argument:tt)+) => {
         $crate::log_with_severity!(
             $crate::Severity::Error,
-            $text
-            $(, $field = $value)*
+            $($argument)+
         )
     };
 }
@@ -272,10 +527,11 @@ macro_rules! log_error {
 /// # Arguments
 ///
 /// Takes one positional argument:
-/// * `text` - The text content of the message
+/// * `text` - The text content of the message, which can be a format string followed by format
+///   arguments
 ///
 /// Additionally, takes a comma-separated list of key-value pairs. The keys correspond to the fields
-/// of the [`MessageBuilder`] type.
+/// of the [`MessageBuilder`] type. See [`log_with_severity!`] for details.
 ///
 /// # Example
 ///
@@ -289,11 +545,10 @@ macro_rules! log_error {
 /// ```
 #[macro_export]
 macro_rules! log_fatal {
-    ($text:expr $(, $field:ident = $value:expr)*) => {
+    ($($argument:tt)+) => {
         $crate::log_with_severity!(
             $crate::Severity::Fatal,
-            $text
-            $(, $field = $value)*
+            $($argument)+
         )
     };
 }
@@ -13,12 +13,47 @@
 // You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
 // not, see <https://www.gnu.org/licenses/>.
 
-use crate::{FromCoreFields, HasSeverity, HasText, IsSeverity};
+use std::{
+    fmt,
+    sync::atomic::{AtomicU64, Ordering},
+    time::SystemTime,
+};
+
+use crate::{
+    FromCoreFields, HasCorrelationId, HasFields, HasId, HasSeverity, HasSeverityMut,
+    HasSourceLocation, HasTags, HasText, HasTextMut, HasTimestamp, IsSeverity,
+};
+
+/// The ID of the next message that is created.
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Gets a new ID that is unique among all the messages created by this process.
+pub(crate) fn next_id() -> u64 {
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Gets the fields that a new message starts with.
+///
+/// With the `tracing-bridge` feature these describe the current `tracing` span. Messages are
+/// enriched when they are created, rather than when they are logged, so that messages sent to a
+/// background logger get the span of the code that logged them.
+pub(crate) fn initial_fields() -> Vec<(String, String)> {
+    #[cfg(feature = "tracing-bridge")]
+    return crate::middleware::current_span_fields();
+
+    #[cfg(not(feature = "tracing-bridge"))]
+    Vec::new()
+}
 
 /// The default message type provided by `got-ur-logs-uwu`.
 ///
 /// You can always define your own, but this one is provided by default.
 ///
+/// Each message is given a unique ID and a timestamp when it is created, see [`HasId`] and
+/// [`HasTimestamp`]. Clones of a message keep its ID and timestamp. With the `tracing-bridge`
+/// feature, messages created inside a `tracing` span also start with fields describing the span,
+/// like those added by `SpanEnricher`.
+///
 /// # Example
 ///
 /// ```
@@ -26,9 +61,18 @@ use crate::{FromCoreFields, HasSeverity, HasText, IsSeverity};
 /// #
 /// Message::from_core_fields(Severity::Info, "hello, world");
 /// ```
+#[derive(Clone, Debug)]
 pub struct Message<Severity: IsSeverity> {
     pub(crate) _severity: Severity,
     pub(crate) _text: String,
+    pub(crate) _tags: Vec<String>,
+    pub(crate) _correlation_id: Option<String>,
+    pub(crate) _fields: Vec<(String, String)>,
+    pub(crate) _timestamp: SystemTime,
+    pub(crate) _source_file: Option<&'static str>,
+    pub(crate) _source_line: Option<u32>,
+    pub(crate) _source_module: Option<&'static str>,
+    pub(crate) _id: u64,
 }
 
 impl<Severity: IsSeverity> HasSeverity<Severity> for Message<Severity> {
@@ -37,21 +81,104 @@ impl<Severity: IsSeverity> HasSeverity<Severity> for Message<Severity> {
     }
 }
 
+impl<Severity: IsSeverity> HasSeverityMut<Severity> for Message<Severity> {
+    fn severity_mut(&mut self) -> &mut Severity {
+        &mut self._severity
+    }
+}
+
 impl<Severity: IsSeverity> HasText for Message<Severity> {
     fn text(&self) -> &str {
         self._text.as_str()
     }
 }
 
+impl<Severity: IsSeverity> HasTextMut for Message<Severity> {
+    fn text_mut(&mut self) -> &mut String {
+        &mut self._text
+    }
+}
+
+impl<Severity: IsSeverity> HasTags for Message<Severity> {
+    fn tags(&self) -> &[String] {
+        self._tags.as_slice()
+    }
+}
+
+impl<Severity: IsSeverity> HasCorrelationId for Message<Severity> {
+    fn correlation_id(&self) -> Option<&str> {
+        self._correlation_id.as_deref()
+    }
+}
+
+impl<Severity: IsSeverity> HasFields for Message<Severity> {
+    fn fields(&self) -> &[(String, String)] {
+        self._fields.as_slice()
+    }
+
+    fn fields_mut(&mut self) -> &mut Vec<(String, String)> {
+        &mut self._fields
+    }
+}
+
+impl<Severity: IsSeverity> HasTimestamp for Message<Severity> {
+    fn timestamp(&self) -> SystemTime {
+        self._timestamp
+    }
+}
+
+impl<Severity: IsSeverity> HasSourceLocation for Message<Severity> {
+    fn source_file(&self) -> Option<&'static str> {
+        self._source_file
+    }
+
+    fn source_line(&self) -> Option<u32> {
+        self._source_line
+    }
+
+    fn source_module(&self) -> Option<&'static str> {
+        self._source_module
+    }
+}
+
+impl<Severity: IsSeverity> HasId for Message<Severity> {
+    fn id(&self) -> u64 {
+        self._id
+    }
+}
+
 impl<Severity: IsSeverity> FromCoreFields<Severity> for Message<Severity> {
     fn from_core_fields(severity: Severity, text: &str) -> Self {
         Message {
             _severity: severity,
             _text: text.to_owned(),
+            _tags: Vec::new(),
+            _correlation_id: None,
+            _fields: initial_fields(),
+            _timestamp: SystemTime::now(),
+            _source_file: None,
+            _source_line: None,
+            _source_module: None,
+            _id: next_id(),
+        }
+    }
+
+    fn from_core_fields_at(severity: Severity, text: &str, timestamp: SystemTime) -> Self {
+        Message {
+            _timestamp: timestamp,
+            ..Self::from_core_fields(severity, text)
         }
     }
 }
 
+/// Displays the message in the same layout as the default plaintext template, like
+/// `[info] hello, world`.
+impl<Severity: IsSeverity> fmt::Display for Message<Severity> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "[{}] {}", self._severity, self._text)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{FromCoreFields, Message, Severity};
@@ -64,5 +191,53 @@ mod tests {
 
         assert_eq!(*message.severity(), Severity::Debug);
         assert_eq!(message.text(), "test");
+        assert!(message.tags().is_empty());
+        assert_eq!(message.correlation_id(), None);
+        assert!(message.fields().is_empty());
+        assert_eq!(message.source_file(), None);
+        assert_eq!(message.source_line(), None);
+        assert_eq!(message.source_module(), None);
+    }
+
+    #[test]
+    fn timestamp() {
+        let before = SystemTime::now();
+        let message = Message::from_core_fields(Severity::Debug, "test");
+        let after = SystemTime::now();
+
+        assert!(before <= message.timestamp());
+        assert!(message.timestamp() <= after);
+    }
+
+    #[test]
+    fn display() {
+        let message = Message::from_core_fields(Severity::DeveloperWarning, "hello, world");
+
+        assert_eq!(message.to_string(), "[dev warning] hello, world");
+    }
+
+    #[test]
+    fn debug() {
+        let message = Message::from_core_fields(Severity::Info, "hello, world");
+
+        assert_eq!(
+            format!("{:?}", message),
+            format!(
+                "Message {{ _severity: Info, _text: \"hello, world\", _tags: [], \
+                 _correlation_id: None, _fields: [], _timestamp: {:?}, _source_file: None, _source_line: None, \
+                 _source_module: None, _id: {} }}",
+                message.timestamp(),
+                message.id()
+            )
+        );
+    }
+
+    #[test]
+    fn unique_ids() {
+        let first = Message::from_core_fields(Severity::Info, "first");
+        let second = Message::from_core_fields(Severity::Info, "second");
+
+        assert_ne!(first.id(), second.id());
+        assert_eq!(first.clone().id(), first.id());
     }
 }
@@ -0,0 +1,112 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+mod context_enricher;
+mod severity_upgrader;
+#[cfg(feature = "tracing-bridge")]
+mod span_enricher;
+mod tag_router;
+
+pub use context_enricher::{ContextEnricher, ContextGuard};
+pub use severity_upgrader::SeverityUpgrader;
+#[cfg(feature = "tracing-bridge")]
+pub(crate) use span_enricher::current_span_fields;
+#[cfg(feature = "tracing-bridge")]
+pub use span_enricher::{SpanEnricher, SpanFieldRecorder};
+pub use tag_router::TagRouter;
+
+use crate::{HasSeverity, HasText, IsSeverity};
+
+/// A trait implemented by all logger middleware.
+///
+/// Middleware is added to a logger with [`crate::Logger::add_middleware`] and runs on every
+/// message that passes the logger's minimum severity, in the order it was added, before the message
+/// is passed along to the writers. Unlike wrapping individual writers, middleware applies to all of
+/// the logger's writers at once.
+///
+/// Closures that take and return messages implement this trait.
+///
+/// # Example
+///
+/// ```
+/// # use got_ur_logs_uwu::{middleware::Middleware, HasText, Message, Severity};
+/// #
+/// /// Drops any messages that mention passwords.
+/// struct RedactPasswords;
+///
+/// impl Middleware<Severity, Message<Severity>> for RedactPasswords {
+///     fn process(&self, message: Message<Severity>) -> Option<Message<Severity>> {
+///         if message.text().contains("password") {
+///             None
+///         } else {
+///             Some(message)
+///         }
+///     }
+/// }
+/// ```
+pub trait Middleware<Severity: IsSeverity, Message: HasSeverity<Severity> + HasText>:
+    Send + Sync
+{
+    /// Processes a message.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message to process
+    ///
+    /// # Returns
+    ///
+    /// Returns the message to pass along, which may have been modified, or `None` to drop it.
+    fn process(&self, message: Message) -> Option<Message>;
+}
+
+impl<
+        Severity: IsSeverity,
+        Message: HasSeverity<Severity> + HasText,
+        Function: Fn(Message) -> Option<Message> + Send + Sync,
+    > Middleware<Severity, Message> for Function
+{
+    fn process(&self, message: Message) -> Option<Message> {
+        self(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FromCoreFields, Message, Severity};
+
+    use super::*;
+
+    #[test]
+    fn closure() {
+        let middleware = |message: Message<Severity>| {
+            if message.text().is_empty() {
+                None
+            } else {
+                Some(message)
+            }
+        };
+
+        assert!(Middleware::<Severity, Message<Severity>>::process(
+            &middleware,
+            Message::from_core_fields(Severity::Info, "")
+        )
+        .is_none());
+        assert!(Middleware::<Severity, Message<Severity>>::process(
+            &middleware,
+            Message::from_core_fields(Severity::Info, "test")
+        )
+        .is_some());
+    }
+}
@@ -0,0 +1,179 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+use std::{cell::RefCell, marker::PhantomData};
+
+use crate::{middleware::Middleware, HasFields, HasSeverity, HasText, IsSeverity};
+
+thread_local! {
+    /// The context fields that have been pushed on the current thread.
+    static CONTEXT: RefCell<Vec<(String, String)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Middleware that adds the current thread's context fields to every message.
+///
+/// Context fields are pushed with [`ContextEnricher::push`] and stay in effect until the returned
+/// guard is dropped. The fields are appended to the message's structured fields in the order they
+/// were pushed.
+///
+/// Context is read from the thread that logs the message, so it is not carried over to other
+/// threads such as the task of a background logger.
+///
+/// # Example
+///
+/// ```
+/// # use got_ur_logs_uwu::{middleware::ContextEnricher, Logger, Message, Severity};
+/// #
+/// # let mut logger = Logger::<Severity, Message<Severity>>::default();
+/// #
+/// logger.add_middleware(ContextEnricher);
+///
+/// {
+///     let _request = ContextEnricher::push("request_id", "42");
+///
+///     logger.log_info("handling request"); // ← This has the field request_id=42
+/// }
+///
+/// logger.log_info("idle"); // ← This has no fields
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ContextEnricher;
+
+impl ContextEnricher {
+    /// Pushes a context field for the current thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the field
+    /// * `value` - The value of the field
+    ///
+    /// # Returns
+    ///
+    /// Returns a guard that removes the field, along with any fields pushed after it, when
+    /// dropped.
+    pub fn push<KeyType: Into<String>, ValueType: Into<String>>(
+        key: KeyType,
+        value: ValueType,
+    ) -> ContextGuard {
+        CONTEXT.with(|context| {
+            let mut context = context.borrow_mut();
+
+            let depth = context.len();
+
+            context.push((key.into(), value.into()));
+
+            ContextGuard {
+                depth,
+                thread_phantom: PhantomData,
+            }
+        })
+    }
+
+    /// Gets the context fields of the current thread.
+    pub fn current() -> Vec<(String, String)> {
+        CONTEXT.with(|context| context.borrow().clone())
+    }
+}
+
+impl<SeverityType: IsSeverity, MessageType: HasSeverity<SeverityType> + HasText + HasFields>
+    Middleware<SeverityType, MessageType> for ContextEnricher
+{
+    fn process(&self, mut message: MessageType) -> Option<MessageType> {
+        CONTEXT.with(|context| {
+            message
+                .fields_mut()
+                .extend(context.borrow().iter().cloned());
+        });
+
+        Some(message)
+    }
+}
+
+/// A guard returned by [`ContextEnricher::push`] that removes the context field when dropped.
+#[must_use = "the context field is removed as soon as the guard is dropped"]
+pub struct ContextGuard {
+    depth: usize,
+    // Context is per-thread, so the guard must be dropped on the thread that created it
+    thread_phantom: PhantomData<*const ()>,
+}
+
+impl Drop for ContextGuard {
+    fn drop(&mut self) {
+        CONTEXT.with(|context| context.borrow_mut().truncate(self.depth));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use crate::{FromCoreFields, Message, Severity};
+
+    use super::*;
+
+    fn enrich(message: Message<Severity>) -> Message<Severity> {
+        Middleware::<Severity, Message<Severity>>::process(&ContextEnricher, message)
+            .expect("context enricher should never drop messages")
+    }
+
+    #[test]
+    fn adds_fields() {
+        let _a = ContextEnricher::push("a", "1");
+        let _b = ContextEnricher::push("b", "2");
+
+        let message = enrich(Message::from_core_fields(Severity::Info, "test"));
+
+        assert_eq!(
+            message.fields(),
+            [
+                ("a".to_owned(), "1".to_owned()),
+                ("b".to_owned(), "2".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn guard_removes_fields() {
+        let a = ContextEnricher::push("a", "1");
+
+        {
+            let _b = ContextEnricher::push("b", "2");
+
+            assert_eq!(ContextEnricher::current().len(), 2);
+        }
+
+        assert_eq!(
+            ContextEnricher::current(),
+            [("a".to_owned(), "1".to_owned())]
+        );
+
+        drop(a);
+
+        assert!(ContextEnricher::current().is_empty());
+    }
+
+    #[test]
+    fn per_thread() {
+        let _a = ContextEnricher::push("a", "1");
+
+        thread::spawn(|| {
+            let message = enrich(Message::from_core_fields(Severity::Info, "test"));
+
+            assert!(message.fields().is_empty());
+        })
+        .join()
+        .unwrap();
+    }
+}
@@ -0,0 +1,167 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    collections::VecDeque,
+    sync::Mutex,
+    time::{Duration, SystemTime},
+};
+
+use crate::{middleware::Middleware, Clock, HasSeverityMut, HasText, IsSeverity, SystemClock};
+
+/// The length of the sliding window that the rate is measured over.
+const WINDOW: Duration = Duration::from_secs(1);
+
+/// Middleware that promotes messages to a higher severity when they are logged too often.
+///
+/// The rate of messages at the `promote_from` severity is measured over a sliding window of one
+/// second. Once it exceeds `threshold_per_second`, subsequent messages at that severity are
+/// changed to the `promote_to` severity until the rate drops again.
+///
+/// # Example
+///
+/// ```
+/// # use got_ur_logs_uwu::{middleware::SeverityUpgrader, Logger, Message, Severity};
+/// #
+/// # let mut logger = Logger::<Severity, Message<Severity>>::default();
+/// #
+/// // A burst of more than 10 errors per second is fatal
+/// logger.add_middleware(SeverityUpgrader::new(10.0, Severity::Error, Severity::Fatal));
+/// ```
+pub struct SeverityUpgrader<SeverityType: IsSeverity> {
+    threshold_per_second: f64,
+    promote_from: SeverityType,
+    promote_to: SeverityType,
+    clock: Box<dyn Clock>,
+    recent: Mutex<VecDeque<SystemTime>>,
+}
+
+impl<SeverityType: IsSeverity> SeverityUpgrader<SeverityType> {
+    /// Create a new severity upgrader.
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold_per_second` - The rate above which messages are promoted
+    /// * `promote_from` - The severity of messages that are counted and promoted
+    /// * `promote_to` - The severity that messages are promoted to
+    pub fn new(
+        threshold_per_second: f64,
+        promote_from: SeverityType,
+        promote_to: SeverityType,
+    ) -> Self {
+        Self {
+            threshold_per_second,
+            promote_from,
+            promote_to,
+            clock: Box::new(SystemClock),
+            recent: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Replaces the clock that is used to measure the rate.
+    ///
+    /// By default this is [`SystemClock`]. Only the time between messages matters, so the clock
+    /// does not need to be synchronized with the wall clock.
+    ///
+    /// # Arguments
+    ///
+    /// * `clock` - The clock to use
+    pub fn with_clock<ClockType: 'static + Clock>(mut self, clock: ClockType) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    /// Records a message and checks whether the rate is above the threshold.
+    fn record(&self) -> bool {
+        let now = self.clock.now();
+
+        let mut recent = self.recent.lock().unwrap();
+
+        while let Some(oldest) = recent.front() {
+            match now.duration_since(*oldest) {
+                Ok(age) if age >= WINDOW => {
+                    recent.pop_front();
+                }
+                _ => break,
+            }
+        }
+
+        recent.push_back(now);
+
+        recent.len() as f64 / WINDOW.as_secs_f64() > self.threshold_per_second
+    }
+}
+
+impl<SeverityType: IsSeverity + Clone, MessageType: HasSeverityMut<SeverityType> + HasText>
+    Middleware<SeverityType, MessageType> for SeverityUpgrader<SeverityType>
+{
+    fn process(&self, mut message: MessageType) -> Option<MessageType> {
+        if message.severity() == &self.promote_from && self.record() {
+            *message.severity_mut() = self.promote_to.clone();
+        }
+
+        Some(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_utils::FakeClock, FromCoreFields, HasSeverity, Message, Severity};
+
+    use super::*;
+
+    fn process(upgrader: &SeverityUpgrader<Severity>, severity: Severity) -> Severity {
+        Middleware::<Severity, Message<Severity>>::process(
+            upgrader,
+            Message::from_core_fields(severity, "test"),
+        )
+        .expect("severity upgrader should never drop messages")
+        .severity()
+        .clone()
+    }
+
+    #[test]
+    fn promotes_above_threshold() {
+        let clock = FakeClock::default();
+
+        let upgrader =
+            SeverityUpgrader::new(2.0, Severity::Error, Severity::Fatal).with_clock(clock.clone());
+
+        assert_eq!(process(&upgrader, Severity::Error), Severity::Error);
+        assert_eq!(process(&upgrader, Severity::Error), Severity::Error);
+        assert_eq!(process(&upgrader, Severity::Error), Severity::Fatal);
+
+        // Other severities are neither counted nor promoted
+        assert_eq!(process(&upgrader, Severity::Warning), Severity::Warning);
+    }
+
+    #[test]
+    fn window_slides() {
+        let clock = FakeClock::default();
+
+        let upgrader =
+            SeverityUpgrader::new(1.0, Severity::Error, Severity::Fatal).with_clock(clock.clone());
+
+        assert_eq!(process(&upgrader, Severity::Error), Severity::Error);
+
+        clock.advance(Duration::from_millis(500));
+
+        assert_eq!(process(&upgrader, Severity::Error), Severity::Fatal);
+
+        clock.advance(Duration::from_millis(1500));
+
+        assert_eq!(process(&upgrader, Severity::Error), Severity::Error);
+    }
+}
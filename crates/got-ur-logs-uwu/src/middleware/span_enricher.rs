@@ -0,0 +1,413 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    any::TypeId,
+    collections::HashMap,
+    fmt,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    subscriber::Interest,
+    Event, Metadata, Subscriber,
+};
+use tracing_core::{span::Current, LevelFilter};
+
+use crate::{middleware::Middleware, HasFields, HasSeverity, HasText, IsSeverity};
+
+/// Middleware that adds the current [`tracing`] span to every message.
+///
+/// Requires the `tracing-bridge` feature. When a message is logged from inside an entered span,
+/// the span's name is added as the `span` field and its ID as the `span_id` field, so that log
+/// entries can be correlated with the spans emitted through `tracing`. If the subscriber is wrapped
+/// in a [`SpanFieldRecorder`], the values recorded on the span are added as fields too. Messages
+/// logged outside of any span, or when no `tracing` subscriber is installed, are left unchanged.
+///
+/// [`Message`](crate::Message) already gets these fields when it is created with the
+/// `tracing-bridge` feature, so this is only needed for other message types.
+///
+/// # Example
+///
+/// ```
+/// # use got_ur_logs_uwu::{middleware::SpanEnricher, Logger, Message, Severity};
+/// #
+/// # let mut logger = Logger::<Severity, Message<Severity>>::default();
+/// #
+/// logger.add_middleware(SpanEnricher);
+///
+/// let span = tracing::info_span!("request");
+/// let _entered = span.enter();
+///
+/// logger.log_info("handling request"); // ← This has the field span=request
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SpanEnricher;
+
+impl<SeverityType: IsSeverity, MessageType: HasSeverity<SeverityType> + HasText + HasFields>
+    Middleware<SeverityType, MessageType> for SpanEnricher
+{
+    fn process(&self, mut message: MessageType) -> Option<MessageType> {
+        // Messages that were already enriched when they were created are left alone
+        if !message.fields().iter().any(|(key, _)| key == "span_id") {
+            message.fields_mut().extend(current_span_fields());
+        }
+
+        Some(message)
+    }
+}
+
+/// Gets the fields that describe the current [`tracing`] span.
+///
+/// This is empty outside of any span or when no `tracing` subscriber is installed.
+pub(crate) fn current_span_fields() -> Vec<(String, String)> {
+    let span = tracing::Span::current();
+
+    let (Some(id), Some(metadata)) = (span.id(), span.metadata()) else {
+        return Vec::new();
+    };
+
+    let mut fields = vec![
+        ("span".to_owned(), metadata.name().to_owned()),
+        ("span_id".to_owned(), id.into_u64().to_string()),
+    ];
+
+    span.with_subscriber(|(id, dispatch)| {
+        if let Some(recorded) = dispatch.downcast_ref::<RecordedSpanFields>() {
+            fields.extend(recorded.get(id));
+        }
+    });
+
+    fields
+}
+
+/// A `tracing` subscriber that keeps the values recorded on spans so that they can be added to
+/// messages.
+///
+/// Requires the `tracing-bridge` feature. It wraps another subscriber, which still receives
+/// everything. While a span recorded by it is current, its values are added to messages as fields
+/// by [`SpanEnricher`] and when a [`Message`](crate::Message) is created.
+///
+/// # Example
+///
+/// ```
+/// # use got_ur_logs_uwu::{middleware::SpanFieldRecorder, HasFields, Logger, Message, Severity};
+/// # use tracing::{subscriber::NoSubscriber, Subscriber};
+/// #
+/// # fn build_subscriber() -> impl Subscriber {
+/// #     NoSubscriber::default()
+/// # }
+/// #
+/// // Wrap whichever subscriber the application already uses
+/// tracing::subscriber::set_global_default(SpanFieldRecorder::new(build_subscriber()))
+///     .expect("a subscriber was already installed");
+/// ```
+pub struct SpanFieldRecorder<SubscriberType: Subscriber> {
+    inner: SubscriberType,
+    recorded: RecordedSpanFields,
+}
+
+impl<SubscriberType: Subscriber> SpanFieldRecorder<SubscriberType> {
+    /// Creates a new recorder that wraps a subscriber.
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The subscriber that spans and events are passed along to
+    pub fn new(inner: SubscriberType) -> Self {
+        Self {
+            inner,
+            recorded: RecordedSpanFields::default(),
+        }
+    }
+}
+
+impl<SubscriberType: Subscriber> Subscriber for SpanFieldRecorder<SubscriberType> {
+    fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
+        self.inner.register_callsite(metadata)
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        self.inner.enabled(metadata)
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        self.inner.max_level_hint()
+    }
+
+    fn new_span(&self, attributes: &Attributes<'_>) -> Id {
+        let id = self.inner.new_span(attributes);
+
+        let mut fields = Vec::new();
+
+        attributes.record(&mut FieldVisitor {
+            fields: &mut fields,
+        });
+
+        self.recorded.lock().insert(id.into_u64(), fields);
+
+        id
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        self.inner.record(span, values);
+
+        if let Some(fields) = self.recorded.lock().get_mut(&span.into_u64()) {
+            values.record(&mut FieldVisitor { fields });
+        }
+    }
+
+    fn record_follows_from(&self, span: &Id, follows: &Id) {
+        self.inner.record_follows_from(span, follows);
+    }
+
+    fn event_enabled(&self, event: &Event<'_>) -> bool {
+        self.inner.event_enabled(event)
+    }
+
+    fn event(&self, event: &Event<'_>) {
+        self.inner.event(event);
+    }
+
+    fn enter(&self, span: &Id) {
+        self.inner.enter(span);
+    }
+
+    fn exit(&self, span: &Id) {
+        self.inner.exit(span);
+    }
+
+    fn clone_span(&self, id: &Id) -> Id {
+        self.inner.clone_span(id)
+    }
+
+    fn try_close(&self, id: Id) -> bool {
+        let closed = self.inner.try_close(id.clone());
+
+        if closed {
+            self.recorded.lock().remove(&id.into_u64());
+        }
+
+        closed
+    }
+
+    fn current_span(&self) -> Current {
+        self.inner.current_span()
+    }
+
+    unsafe fn downcast_raw(&self, id: TypeId) -> Option<*const ()> {
+        // The recorded fields are exposed separately so that they can be found without knowing the
+        // type of the wrapped subscriber
+        if id == TypeId::of::<Self>() {
+            Some(self as *const Self as *const ())
+        } else if id == TypeId::of::<RecordedSpanFields>() {
+            Some(&self.recorded as *const RecordedSpanFields as *const ())
+        } else {
+            self.inner.downcast_raw(id)
+        }
+    }
+}
+
+/// The values recorded on each open span, by span ID.
+#[derive(Default)]
+struct RecordedSpanFields {
+    spans: Mutex<HashMap<u64, Vec<(String, String)>>>,
+}
+
+impl RecordedSpanFields {
+    /// Locks the recorded values.
+    fn lock(&self) -> MutexGuard<'_, HashMap<u64, Vec<(String, String)>>> {
+        self.spans.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Gets the values recorded on a span.
+    fn get(&self, id: &Id) -> Vec<(String, String)> {
+        self.lock().get(&id.into_u64()).cloned().unwrap_or_default()
+    }
+}
+
+/// Records span values as strings, replacing earlier values of the same field.
+struct FieldVisitor<'fields> {
+    fields: &'fields mut Vec<(String, String)>,
+}
+
+impl FieldVisitor<'_> {
+    fn insert(&mut self, field: &Field, value: String) {
+        match self.fields.iter_mut().find(|(key, _)| key == field.name()) {
+            Some((_, existing)) => *existing = value,
+            None => self.fields.push((field.name().to_owned(), value)),
+        }
+    }
+}
+
+impl Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, value.to_owned());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.insert(field, format!("{:?}", value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing::subscriber::with_default;
+
+    use crate::{FromCoreFields, Message, Severity};
+
+    use super::*;
+
+    /// A subscriber that only keeps track of which span is entered.
+    #[derive(Default)]
+    struct CurrentSpanSubscriber {
+        spans: Mutex<Vec<&'static Metadata<'static>>>,
+        current: Mutex<Vec<Id>>,
+    }
+
+    impl Subscriber for CurrentSpanSubscriber {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, attributes: &Attributes<'_>) -> Id {
+            let mut spans = self.spans.lock().unwrap();
+
+            spans.push(attributes.metadata());
+
+            Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, _event: &Event<'_>) {}
+
+        fn enter(&self, span: &Id) {
+            self.current.lock().unwrap().push(span.clone());
+        }
+
+        fn exit(&self, _span: &Id) {
+            self.current.lock().unwrap().pop();
+        }
+
+        fn current_span(&self) -> Current {
+            match self.current.lock().unwrap().last() {
+                Some(id) => {
+                    let metadata = self.spans.lock().unwrap()[id.into_u64() as usize - 1];
+
+                    Current::new(id.clone(), metadata)
+                }
+                None => Current::none(),
+            }
+        }
+    }
+
+    fn enrich(message: Message<Severity>) -> Message<Severity> {
+        Middleware::<Severity, Message<Severity>>::process(&SpanEnricher, message)
+            .expect("span enricher should never drop messages")
+    }
+
+    #[test]
+    fn adds_current_span() {
+        with_default(CurrentSpanSubscriber::default(), || {
+            let _outer = tracing::info_span!("outer").entered();
+            let _inner = tracing::info_span!("inner").entered();
+
+            let message = enrich(Message::from_core_fields(Severity::Info, "test"));
+
+            assert_eq!(
+                message.fields(),
+                [
+                    ("span".to_owned(), "inner".to_owned()),
+                    ("span_id".to_owned(), "2".to_owned())
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn adds_recorded_values() {
+        with_default(
+            SpanFieldRecorder::new(CurrentSpanSubscriber::default()),
+            || {
+                let span =
+                    tracing::info_span!("request", user_id = 42, path = tracing::field::Empty);
+
+                span.record("path", "/login");
+
+                let _entered = span.enter();
+
+                let mut message = Message::from_core_fields(Severity::Info, "test");
+
+                message.fields_mut().clear();
+
+                assert_eq!(
+                    enrich(message).fields(),
+                    [
+                        ("span".to_owned(), "request".to_owned()),
+                        ("span_id".to_owned(), "1".to_owned()),
+                        ("user_id".to_owned(), "42".to_owned()),
+                        ("path".to_owned(), "/login".to_owned())
+                    ]
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn messages_are_enriched_when_created() {
+        with_default(
+            SpanFieldRecorder::new(CurrentSpanSubscriber::default()),
+            || {
+                let _entered = tracing::info_span!("request", user_id = 42).entered();
+
+                let message = Message::from_core_fields(Severity::Info, "test");
+
+                assert_eq!(
+                    message.fields(),
+                    [
+                        ("span".to_owned(), "request".to_owned()),
+                        ("span_id".to_owned(), "1".to_owned()),
+                        ("user_id".to_owned(), "42".to_owned())
+                    ]
+                );
+
+                // The middleware does not add the span a second time
+                assert_eq!(enrich(message).fields().len(), 3);
+            },
+        );
+    }
+
+    #[test]
+    fn outside_of_span() {
+        with_default(CurrentSpanSubscriber::default(), || {
+            let message = enrich(Message::from_core_fields(Severity::Info, "test"));
+
+            assert!(message.fields().is_empty());
+        });
+    }
+
+    #[test]
+    fn without_subscriber() {
+        let _span = tracing::info_span!("request").entered();
+
+        let message = enrich(Message::from_core_fields(Severity::Info, "test"));
+
+        assert!(message.fields().is_empty());
+    }
+}
@@ -0,0 +1,161 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{middleware::Middleware, HasSeverity, HasTags, HasText, IsSeverity, Logger};
+
+/// Middleware that forwards tagged messages to other loggers.
+///
+/// Each route maps a tag to a sub-logger. A message is forwarded to the sub-logger of the first of
+/// its tags that has a route. When `passthrough` is enabled, routed messages also continue on to
+/// the main logger's writers, otherwise they are only written by the sub-logger. Messages without
+/// a routed tag always continue on to the main logger's writers.
+///
+/// # Example
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use got_ur_logs_uwu::{
+/// #     middleware::TagRouter, writers::ConsoleWriter, Logger, Message, Severity,
+/// #     formatters::Plaintext
+/// # };
+/// #
+/// let mut http_logger = Logger::<Severity, Message<Severity>>::default();
+///
+/// http_logger.add_writer(ConsoleWriter::new_stderr(Plaintext::new_default()));
+///
+/// let mut logger = Logger::<Severity, Message<Severity>>::default();
+///
+/// // Messages tagged "http" only go to stderr
+/// logger.add_middleware(TagRouter::new(false).with_route("http", Arc::new(http_logger)));
+/// ```
+pub struct TagRouter<SeverityType: IsSeverity, MessageType: HasSeverity<SeverityType> + HasText> {
+    routes: HashMap<String, Arc<Logger<SeverityType, MessageType>>>,
+    passthrough: bool,
+}
+
+impl<SeverityType: IsSeverity, MessageType: HasSeverity<SeverityType> + HasText>
+    TagRouter<SeverityType, MessageType>
+{
+    /// Create a new tag router without any routes.
+    ///
+    /// # Arguments
+    ///
+    /// * `passthrough` - Whether routed messages should also be passed along to the main logger's
+    ///   writers
+    pub fn new(passthrough: bool) -> Self {
+        Self {
+            routes: HashMap::new(),
+            passthrough,
+        }
+    }
+
+    /// Adds a route, replacing any existing route for the same tag.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The tag of messages to route
+    /// * `logger` - The sub-logger to forward the messages to
+    pub fn with_route<StringType: Into<String>>(
+        mut self,
+        tag: StringType,
+        logger: Arc<Logger<SeverityType, MessageType>>,
+    ) -> Self {
+        self.routes.insert(tag.into(), logger);
+        self
+    }
+}
+
+impl<
+        SeverityType: IsSeverity,
+        MessageType: HasSeverity<SeverityType> + HasText + HasTags + Clone,
+    > Middleware<SeverityType, MessageType> for TagRouter<SeverityType, MessageType>
+{
+    fn process(&self, message: MessageType) -> Option<MessageType> {
+        let Some(logger) = message.tags().iter().find_map(|tag| self.routes.get(tag)) else {
+            return Some(message);
+        };
+
+        if self.passthrough {
+            logger.log_message(message.clone());
+
+            Some(message)
+        } else {
+            logger.log_message(message);
+
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use crate::{writers::CaptureWriter, FromCoreFields, Message, Severity};
+
+    use super::*;
+
+    type SubLogger = Arc<Logger<Severity, Message<Severity>>>;
+
+    fn tagged(tag: &str) -> Message<Severity> {
+        let mut message = Message::from_core_fields(Severity::Info, "test");
+
+        message._tags.push(tag.to_owned());
+
+        message
+    }
+
+    fn sub_logger() -> (SubLogger, Arc<Mutex<CaptureWriter<Severity>>>) {
+        let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+        let mut logger = Logger::default();
+
+        logger.add_writer_shared(writer.clone());
+
+        (Arc::new(logger), writer)
+    }
+
+    #[test]
+    fn routes_first_matching_tag() {
+        let (http_logger, http_writer) = sub_logger();
+        let (db_logger, db_writer) = sub_logger();
+
+        let router = TagRouter::new(false)
+            .with_route("http", http_logger)
+            .with_route("db", db_logger);
+
+        let mut message = tagged("db");
+        message._tags.push("http".to_owned());
+
+        assert!(router.process(message).is_none());
+        assert!(router.process(tagged("other")).is_some());
+
+        assert_eq!(http_writer.lock().unwrap().message_count(), 0);
+        assert_eq!(db_writer.lock().unwrap().message_count(), 1);
+    }
+
+    #[test]
+    fn passthrough() {
+        let (http_logger, http_writer) = sub_logger();
+
+        let router = TagRouter::new(true).with_route("http", http_logger);
+
+        assert!(router.process(tagged("http")).is_some());
+
+        assert_eq!(http_writer.lock().unwrap().message_count(), 1);
+    }
+}
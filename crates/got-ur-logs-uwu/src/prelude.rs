@@ -0,0 +1,38 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+//! Re-exports the most commonly used items.
+//!
+//! # Example
+//!
+//! ```
+//! use got_ur_logs_uwu::prelude::*;
+//!
+//! Logger::<Severity, Message<Severity>>::global_mut()
+//!     .add_writer(
+//!         ConsoleWriter::new_stdout(
+//!             Plaintext::new_default()
+//!         )
+//!     );
+//!
+//! log_info!("hello, world");
+//! ```
+
+pub use crate::{
+    formatters::Plaintext, log_debug, log_developer_warning, log_error, log_fatal, log_info,
+    log_message, log_trace, log_warning, writers::ConsoleWriter, Format, FromCoreFields, HasFields,
+    HasSeverity, HasSourceLocation, HasTags, HasText, HasTimestamp, IsSeverity, Logger, Message,
+    Severity, Write,
+};
@@ -15,4 +15,4 @@
 
 mod message_builder;
 
-pub use message_builder::MessageBuilder;
+pub use message_builder::{IntoField, MessageBuilder};
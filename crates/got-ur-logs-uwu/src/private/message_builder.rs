@@ -13,38 +13,283 @@
 // You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
 // not, see <https://www.gnu.org/licenses/>.
 
-use crate::{IsSeverity, Message};
+use std::time::SystemTime;
+
+use crate::{
+    message::{initial_fields, next_id},
+    BuildMessage, IsSeverity, Message,
+};
 
 /// A builder for [`Message`].
 ///
 /// This is used by the macros to set fields as key-value pairs.
-#[derive(Default)]
-pub struct MessageBuilder<SeverityType: Default> {
+pub struct MessageBuilder<SeverityType> {
     pub severity: Option<SeverityType>,
-    pub text: Option<&'static str>,
+    pub text: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub correlation_id: Option<String>,
+    pub fields: Option<Vec<(String, String)>>,
+    pub timestamp: Option<SystemTime>,
+    pub source_file: Option<&'static str>,
+    pub source_line: Option<u32>,
+    pub source_module: Option<&'static str>,
+}
+
+// This is implemented manually because deriving it would require `SeverityType: Default`, which
+// custom severity types are not required to implement
+impl<SeverityType> Default for MessageBuilder<SeverityType> {
+    fn default() -> Self {
+        Self {
+            severity: None,
+            text: None,
+            tags: None,
+            correlation_id: None,
+            fields: None,
+            timestamp: None,
+            source_file: None,
+            source_line: None,
+            source_module: None,
+        }
+    }
+}
+
+impl<SeverityType> MessageBuilder<SeverityType> {
+    /// Creates a builder with only the source location set.
+    ///
+    /// The macros use this as the base for the fields they are given.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - The path of the source file, from [`file!`]
+    /// * `line` - The line number, from [`line!`]
+    /// * `module` - The path of the module, from [`module_path!`]
+    pub fn at_source_location(file: &'static str, line: u32, module: &'static str) -> Self {
+        Self {
+            source_file: Some(file),
+            source_line: Some(line),
+            source_module: Some(module),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the severity from any value that can be converted into the severity type.
+    ///
+    /// # Arguments
+    ///
+    /// * `severity` - The severity, or a value that converts into it
+    pub fn severity_from<ValueType: Into<SeverityType>>(mut self, severity: ValueType) -> Self {
+        self.severity = Some(severity.into());
+        self
+    }
 }
 
-impl<SeverityType: IsSeverity + Default> MessageBuilder<SeverityType> {
-    pub fn build(self) -> Message<SeverityType> {
+impl<SeverityType: IsSeverity> BuildMessage<Message<SeverityType>>
+    for MessageBuilder<SeverityType>
+{
+    fn build(self) -> Message<SeverityType> {
         Message {
             _severity: self.severity.expect("severity must be set"),
-            _text: self.text.expect("text must be set").to_owned(),
+            _text: self.text.expect("text must be set"),
+            _tags: self.tags.unwrap_or_default(),
+            _correlation_id: self.correlation_id,
+            _fields: initial_fields()
+                .into_iter()
+                .chain(self.fields.unwrap_or_default())
+                .collect(),
+            _timestamp: self.timestamp.unwrap_or_else(SystemTime::now),
+            _source_file: self.source_file,
+            _source_line: self.source_line,
+            _source_module: self.source_module,
+            _id: next_id(),
         }
     }
 }
 
+/// A conversion used by the macros to convert the values given for [`MessageBuilder`] fields.
+///
+/// This lets the macros accept convenient values like `vec!["http", "slow"]` for fields that store
+/// owned types.
+pub trait IntoField<FieldType> {
+    fn into_field(self) -> FieldType;
+}
+
+impl<FieldType> IntoField<FieldType> for FieldType {
+    fn into_field(self) -> FieldType {
+        self
+    }
+}
+
+impl IntoField<String> for &str {
+    fn into_field(self) -> String {
+        self.to_owned()
+    }
+}
+
+impl IntoField<Vec<String>> for Vec<&str> {
+    fn into_field(self) -> Vec<String> {
+        self.into_iter().map(|value| value.to_owned()).collect()
+    }
+}
+
+impl IntoField<Vec<(String, String)>> for Vec<(&str, &str)> {
+    fn into_field(self) -> Vec<(String, String)> {
+        self.into_iter()
+            .map(|(key, value)| (key.to_owned(), value.to_owned()))
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::Severity;
+    use std::{fmt, time::Duration};
+
+    use crate::{
+        HasCorrelationId, HasFields, HasSeverity, HasSourceLocation, HasTags, HasText,
+        HasTimestamp, Severity,
+    };
 
     use super::*;
 
     #[test]
     fn build_message() {
-        MessageBuilder::<Severity> {
+        let message: Message<Severity> = MessageBuilder::<Severity> {
+            severity: Some(Severity::Info),
+            text: Some("test".to_owned()),
+            tags: None,
+            correlation_id: None,
+            fields: None,
+            timestamp: None,
+            source_file: None,
+            source_line: None,
+            source_module: None,
+        }
+        .build();
+
+        assert_eq!(message.text(), "test");
+    }
+
+    #[test]
+    fn build_message_with_severity_from() {
+        #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+        struct Level(u8);
+
+        impl fmt::Display for Level {
+            fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(formatter, "level {}", self.0)
+            }
+        }
+
+        impl From<u8> for Level {
+            fn from(value: u8) -> Self {
+                Self(value)
+            }
+        }
+
+        impl IsSeverity for Level {
+            fn min() -> Self {
+                Self(u8::MIN)
+            }
+
+            fn max() -> Self {
+                Self(u8::MAX)
+            }
+        }
+
+        let message: Message<Level> = MessageBuilder {
+            text: Some("test".to_owned()),
+            ..Default::default()
+        }
+        .severity_from(3)
+        .build();
+
+        assert_eq!(*message.severity(), Level(3));
+    }
+
+    #[test]
+    fn build_message_with_tags() {
+        let message = MessageBuilder::<Severity> {
+            severity: Some(Severity::Info),
+            text: Some("test".to_owned()),
+            tags: Some(vec!["http", "slow"].into_field()),
+            correlation_id: None,
+            fields: None,
+            timestamp: None,
+            source_file: None,
+            source_line: None,
+            source_module: None,
+        }
+        .build();
+
+        assert_eq!(message.tags(), ["http", "slow"]);
+    }
+
+    #[test]
+    fn build_message_with_correlation_id() {
+        let message = MessageBuilder::<Severity> {
             severity: Some(Severity::Info),
-            text: Some("test"),
+            text: Some("test".to_owned()),
+            tags: None,
+            correlation_id: Some("request-1".into_field()),
+            fields: None,
+            timestamp: None,
+            source_file: None,
+            source_line: None,
+            source_module: None,
         }
         .build();
+
+        assert_eq!(message.correlation_id(), Some("request-1"));
+    }
+
+    #[test]
+    fn build_message_with_fields() {
+        let message = MessageBuilder::<Severity> {
+            severity: Some(Severity::Info),
+            text: Some("test".to_owned()),
+            tags: None,
+            correlation_id: None,
+            fields: Some(vec![("user_id", "42")].into_field()),
+            timestamp: None,
+            source_file: None,
+            source_line: None,
+            source_module: None,
+        }
+        .build();
+
+        assert_eq!(message.fields(), [("user_id".to_owned(), "42".to_owned())]);
+    }
+
+    #[test]
+    fn build_message_with_timestamp() {
+        let timestamp = SystemTime::UNIX_EPOCH + Duration::from_secs(1_696_075_200);
+
+        let message = MessageBuilder::<Severity> {
+            severity: Some(Severity::Info),
+            text: Some("test".to_owned()),
+            tags: None,
+            correlation_id: None,
+            fields: None,
+            timestamp: Some(timestamp),
+            source_file: None,
+            source_line: None,
+            source_module: None,
+        }
+        .build();
+
+        assert_eq!(message.timestamp(), timestamp);
+    }
+
+    #[test]
+    fn build_message_at_source_location() {
+        let message: Message<Severity> = MessageBuilder {
+            severity: Some(Severity::Info),
+            text: Some("test".to_owned()),
+            ..MessageBuilder::at_source_location("src/main.rs", 42, "app")
+        }
+        .build();
+
+        assert_eq!(message.source_file(), Some("src/main.rs"));
+        assert_eq!(message.source_line(), Some(42));
+        assert_eq!(message.source_module(), Some("app"));
     }
 }
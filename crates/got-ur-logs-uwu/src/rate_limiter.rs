@@ -0,0 +1,165 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// A lock-free token bucket rate limiter.
+///
+/// The bucket holds up to one second's worth of tokens, so short bursts are allowed as long as the
+/// average rate stays under the limit.
+pub(crate) struct RateLimiter {
+    max_per_second: f64,
+    capacity: f64,
+    /// The number of tokens in the bucket, stored as the bits of an `f64`.
+    tokens: AtomicU64,
+    /// When tokens were last added to the bucket, in nanoseconds since the Unix epoch.
+    last_refill: AtomicU64,
+    dropped: AtomicU64,
+}
+
+/// Converts a time to nanoseconds since the Unix epoch, saturating for times out of range.
+fn nanos_since_epoch(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX))
+        .unwrap_or(0)
+}
+
+impl RateLimiter {
+    /// Create a new rate limiter with a full bucket.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_per_second` - The average number of messages allowed per second
+    /// * `now` - The current time
+    pub(crate) fn new(max_per_second: f64, now: SystemTime) -> Self {
+        // Always allow at least one message so that rates below one per second work
+        let capacity = max_per_second.max(1.0);
+
+        Self {
+            max_per_second,
+            capacity,
+            tokens: AtomicU64::new(capacity.to_bits()),
+            last_refill: AtomicU64::new(nanos_since_epoch(now)),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// Tries to take a token from the bucket.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The current time
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the message is allowed, or `false` if it should be dropped.
+    pub(crate) fn try_acquire(&self, now: SystemTime) -> bool {
+        self.refill(nanos_since_epoch(now));
+
+        let acquired = self
+            .tokens
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |tokens| {
+                let tokens = f64::from_bits(tokens);
+
+                (tokens >= 1.0).then(|| (tokens - 1.0).to_bits())
+            })
+            .is_ok();
+
+        if !acquired {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+
+        acquired
+    }
+
+    /// Gets the number of messages that have been dropped.
+    pub(crate) fn dropped_count(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Adds the tokens earned since the last refill to the bucket.
+    fn refill(&self, now: u64) {
+        let last_refill = self.last_refill.load(Ordering::Acquire);
+
+        if now <= last_refill {
+            return;
+        }
+
+        // Only the thread that claims the elapsed time adds the tokens for it
+        if self
+            .last_refill
+            .compare_exchange(last_refill, now, Ordering::AcqRel, Ordering::Acquire)
+            .is_err()
+        {
+            return;
+        }
+
+        let earned = (now - last_refill) as f64 / 1e9 * self.max_per_second;
+
+        // The closure always returns `Some`, so this cannot fail
+        let _ = self
+            .tokens
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |tokens| {
+                Some(
+                    (f64::from_bits(tokens) + earned)
+                        .min(self.capacity)
+                        .to_bits(),
+                )
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn allows_burst_up_to_capacity() {
+        let rate_limiter = RateLimiter::new(3.0, UNIX_EPOCH);
+
+        assert!(rate_limiter.try_acquire(UNIX_EPOCH));
+        assert!(rate_limiter.try_acquire(UNIX_EPOCH));
+        assert!(rate_limiter.try_acquire(UNIX_EPOCH));
+        assert!(!rate_limiter.try_acquire(UNIX_EPOCH));
+
+        assert_eq!(rate_limiter.dropped_count(), 1);
+    }
+
+    #[test]
+    fn refills_over_time() {
+        let rate_limiter = RateLimiter::new(2.0, UNIX_EPOCH);
+
+        assert!(rate_limiter.try_acquire(UNIX_EPOCH));
+        assert!(rate_limiter.try_acquire(UNIX_EPOCH));
+        assert!(!rate_limiter.try_acquire(UNIX_EPOCH));
+
+        let later = UNIX_EPOCH + Duration::from_millis(500);
+
+        assert!(rate_limiter.try_acquire(later));
+        assert!(!rate_limiter.try_acquire(later));
+
+        // The bucket never holds more than its capacity
+        let much_later = UNIX_EPOCH + Duration::from_secs(60);
+
+        assert!(rate_limiter.try_acquire(much_later));
+        assert!(rate_limiter.try_acquire(much_later));
+        assert!(!rate_limiter.try_acquire(much_later));
+    }
+}
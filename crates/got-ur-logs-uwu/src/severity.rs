@@ -13,16 +13,55 @@
 // You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
 // not, see <https://www.gnu.org/licenses/>.
 
+mod numeric;
+
+pub use numeric::NumericSeverity;
+
 use crate::{
     HasDebugSeverity, HasDeveloperWarningSeverity, HasErrorSeverity, HasFatalSeverity,
     HasInfoSeverity, HasTraceSeverity, HasWarningSeverity, IsSeverity,
 };
-use strum_macros::Display;
+use std::{cmp::Ordering, fmt};
+use strum_macros::AsRefStr;
 
 /// The default severity type provided by `got-ur-logs-uwu`.
 ///
 /// You can always define your own, but this one is provided by default.
-#[derive(Debug, Display, PartialEq, PartialOrd)]
+///
+/// `Debug` shows the variant name, like `DeveloperWarning`, while `Display` shows the name used
+/// in log output, like `dev warning`.
+///
+/// # Default
+///
+/// The default severity is [`Severity::Info`] rather than [`Severity::Trace`]. Info is the most
+/// common severity for operational messages, so it is the most sensible choice when a severity
+/// has not been chosen explicitly. Note that [`IsSeverity`] does not require [`Default`], so
+/// generic code should use [`IsSeverity::min`] or the `Has*Severity` traits instead of relying on
+/// it.
+///
+/// ```
+/// # use got_ur_logs_uwu::Severity;
+/// #
+/// assert_eq!(Severity::default(), Severity::Info);
+/// ```
+///
+/// # Custom severities
+///
+/// Applications that need one or two extra severities can use [`Severity::Custom`] instead of
+/// defining their own severity type. Severities are ordered by their [`Severity::level`], and
+/// severities with the same level are ordered by name. Two severities are equal when they have
+/// the same level and name.
+///
+/// ```
+/// # use got_ur_logs_uwu::Severity;
+/// #
+/// const AUDIT: Severity = Severity::Custom(140, "audit");
+///
+/// assert!(AUDIT > Severity::Info);
+/// assert!(AUDIT < Severity::Warning);
+/// assert_eq!(AUDIT.to_string(), "audit");
+/// ```
+#[derive(AsRefStr, Clone, Debug)]
 pub enum Severity {
     /// Trace messages are used for highly verbose tracing of code that is only applicable for
     /// developers tracing program execution.
@@ -50,14 +89,312 @@ pub enum Severity {
     /// continue.
     #[strum(serialize = "fatal")]
     Fatal,
+    /// A custom severity with a numeric level, which determines how it is ordered, and a display
+    /// name, like `Severity::Custom(140, "audit")`.
+    ///
+    /// See [`Severity::level`] for the levels of the other severities. A custom severity is ordered
+    /// after a built-in severity with the same level, and custom severities with the same level
+    /// are ordered by name. Custom severities are always ordered strictly between
+    /// [`Severity::Trace`] and [`Severity::Fatal`], even with a level of 0 or 255, so that those
+    /// stay the minimum and maximum.
+    ///
+    /// Custom severities are displayed with their name, but [`AsRef<str>`] returns `"custom"` for
+    /// all of them.
+    #[strum(serialize = "custom")]
+    Custom(u8, &'static str),
 }
 
+impl Severity {
+    /// The name of [`Severity::Trace`] in log output.
+    pub const TRACE_STR: &'static str = "trace";
+    /// The name of [`Severity::Debug`] in log output.
+    pub const DEBUG_STR: &'static str = "debug";
+    /// The name of [`Severity::DeveloperWarning`] in log output.
+    pub const DEVELOPER_WARNING_STR: &'static str = "dev warning";
+    /// The name of [`Severity::Info`] in log output.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::Severity;
+    /// #
+    /// assert_eq!(Severity::Info.to_string(), Severity::INFO_STR);
+    /// ```
+    pub const INFO_STR: &'static str = "info";
+    /// The name of [`Severity::Warning`] in log output.
+    pub const WARNING_STR: &'static str = "warning";
+    /// The name of [`Severity::Error`] in log output.
+    pub const ERROR_STR: &'static str = "error";
+    /// The name of [`Severity::Fatal`] in log output.
+    pub const FATAL_STR: &'static str = "fatal";
+
+    /// All the severities, from least to most critical.
+    const ALL: [Self; 7] = [
+        Self::Trace,
+        Self::Debug,
+        Self::DeveloperWarning,
+        Self::Info,
+        Self::Warning,
+        Self::Error,
+        Self::Fatal,
+    ];
+
+    /// Gets the index of the severity, from `0` for [`Severity::Trace`] to `6` for
+    /// [`Severity::Fatal`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::Severity;
+    /// #
+    /// assert_eq!(Severity::Info.as_index(), 3);
+    /// ```
+    ///
+    /// Custom severities have the index of the most critical built-in severity whose level is not
+    /// above theirs.
+    pub fn as_index(&self) -> usize {
+        match self {
+            Self::Trace => 0,
+            Self::Debug => 1,
+            Self::DeveloperWarning => 2,
+            Self::Info => 3,
+            Self::Warning => 4,
+            Self::Error => 5,
+            Self::Fatal => 6,
+            Self::Custom(level, _) => Self::ALL
+                .iter()
+                .rposition(|severity| severity.level() <= *level)
+                .expect("trace has the lowest possible level"),
+        }
+    }
+
+    /// Gets the numeric level of the severity, which determines how severities are ordered.
+    ///
+    /// The built-in severities are spread out so that custom severities can be placed between
+    /// them:
+    ///
+    /// | Severity           | Level |
+    /// |--------------------|-------|
+    /// | `Trace`            | 0     |
+    /// | `Debug`            | 40    |
+    /// | `DeveloperWarning` | 80    |
+    /// | `Info`             | 120   |
+    /// | `Warning`          | 160   |
+    /// | `Error`            | 200   |
+    /// | `Fatal`            | 255   |
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::Severity;
+    /// #
+    /// assert_eq!(Severity::Info.level(), 120);
+    /// assert_eq!(Severity::Custom(140, "audit").level(), 140);
+    /// ```
+    pub fn level(&self) -> u8 {
+        match self {
+            Self::Trace => 0,
+            Self::Debug => 40,
+            Self::DeveloperWarning => 80,
+            Self::Info => 120,
+            Self::Warning => 160,
+            Self::Error => 200,
+            Self::Fatal => 255,
+            Self::Custom(level, _) => *level,
+        }
+    }
+
+    /// Checks whether the severity is [`Severity::Debug`] or less critical.
+    ///
+    /// This is useful to decide whether to include expensive context in a message.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::Severity;
+    /// #
+    /// assert!(Severity::Trace.is_debug_or_below());
+    /// assert!(!Severity::Info.is_debug_or_below());
+    /// ```
+    pub fn is_debug_or_below(&self) -> bool {
+        self.level() <= Self::Debug.level()
+    }
+
+    /// Checks whether the severity is only meant for developers, which is the same as
+    /// [`Severity::is_debug_or_below`].
+    pub fn is_verbose(&self) -> bool {
+        self.is_debug_or_below()
+    }
+
+    /// Checks whether the severity is [`Severity::Info`] or more critical, meaning that it is
+    /// relevant to everyone operating the program.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::Severity;
+    /// #
+    /// assert!(Severity::Warning.is_operational());
+    /// assert!(!Severity::DeveloperWarning.is_operational());
+    /// ```
+    pub fn is_operational(&self) -> bool {
+        self.level() >= Self::Info.level()
+    }
+
+    /// Gets the severity with the given index, as returned by [`Severity::as_index`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::Severity;
+    /// #
+    /// assert_eq!(Severity::from_index(3), Some(Severity::Info));
+    /// assert_eq!(Severity::from_index(7), None);
+    /// ```
+    pub fn from_index(index: usize) -> Option<Self> {
+        Self::ALL.get(index).cloned()
+    }
+
+    /// Converts a [`log::Level`] from the `log` crate into a severity.
+    ///
+    /// Requires the `log-bridge` feature. Every `log` level has an equivalent severity, but
+    /// [`Severity::DeveloperWarning`] and [`Severity::Fatal`] are never produced since the `log`
+    /// crate has no equivalent for them.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - The `log` level to convert
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::Severity;
+    /// #
+    /// assert_eq!(Severity::from_log_level(log::Level::Warn), Severity::Warning);
+    /// ```
+    #[cfg(feature = "log-bridge")]
+    pub fn from_log_level(level: log::Level) -> Self {
+        match level {
+            log::Level::Error => Self::Error,
+            log::Level::Warn => Self::Warning,
+            log::Level::Info => Self::Info,
+            log::Level::Debug => Self::Debug,
+            log::Level::Trace => Self::Trace,
+        }
+    }
+
+    /// Converts the severity into a [`log::Level`] from the `log` crate.
+    ///
+    /// Requires the `log-bridge` feature. This is the reverse of [`Severity::from_log_level`].
+    ///
+    /// # Returns
+    ///
+    /// Returns the equivalent `log` level, or `None` for [`Severity::DeveloperWarning`],
+    /// [`Severity::Fatal`], and [`Severity::Custom`] since the `log` crate has no equivalent for
+    /// them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::Severity;
+    /// #
+    /// assert_eq!(Severity::Warning.to_log_level(), Some(log::Level::Warn));
+    /// assert_eq!(Severity::Fatal.to_log_level(), None);
+    /// ```
+    #[cfg(feature = "log-bridge")]
+    pub fn to_log_level(&self) -> Option<log::Level> {
+        match self {
+            Self::Trace => Some(log::Level::Trace),
+            Self::Debug => Some(log::Level::Debug),
+            Self::DeveloperWarning => None,
+            Self::Info => Some(log::Level::Info),
+            Self::Warning => Some(log::Level::Warn),
+            Self::Error => Some(log::Level::Error),
+            Self::Fatal => None,
+            Self::Custom(..) => None,
+        }
+    }
+}
+
+// Kept as a manual implementation so that the default is visible next to the other impls
+#[allow(clippy::derivable_impls)]
 impl Default for Severity {
     fn default() -> Self {
         Self::Info
     }
 }
 
+impl fmt::Display for Severity {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Custom(_, name) => formatter.pad(name),
+            _ => formatter.pad(self.as_ref()),
+        }
+    }
+}
+
+impl PartialEq for Severity {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl Severity {
+    /// Gets what severities are ordered by before custom severities are compared by name.
+    ///
+    /// The first element keeps [`Severity::Trace`] and [`Severity::Fatal`] below and above every
+    /// custom severity, even those with the same level.
+    fn ordering_key(&self) -> (u8, u8, bool) {
+        match self {
+            Self::Trace => (0, self.level(), false),
+            Self::Fatal => (2, self.level(), false),
+            Self::Custom(level, _) => (1, *level, true),
+            _ => (1, self.level(), false),
+        }
+    }
+}
+
+impl PartialOrd for Severity {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(
+            self.ordering_key()
+                .cmp(&other.ordering_key())
+                .then_with(|| match (self, other) {
+                    (Self::Custom(_, name), Self::Custom(_, other_name)) => name.cmp(other_name),
+                    _ => Ordering::Equal,
+                }),
+        )
+    }
+}
+
+/// Converts severities to their index, as returned by [`Severity::as_index`].
+///
+/// Requires the `num-traits` feature.
+#[cfg(feature = "num-traits")]
+impl num_traits::ToPrimitive for Severity {
+    fn to_i64(&self) -> Option<i64> {
+        i64::try_from(self.as_index()).ok()
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        u64::try_from(self.as_index()).ok()
+    }
+}
+
+/// Converts indices, as returned by [`Severity::as_index`], to severities.
+///
+/// Requires the `num-traits` feature.
+#[cfg(feature = "num-traits")]
+impl num_traits::FromPrimitive for Severity {
+    fn from_i64(value: i64) -> Option<Self> {
+        usize::try_from(value).ok().and_then(Self::from_index)
+    }
+
+    fn from_u64(value: u64) -> Option<Self> {
+        usize::try_from(value).ok().and_then(Self::from_index)
+    }
+}
+
 impl IsSeverity for Severity {
     fn min() -> Self {
         Self::Trace
@@ -80,10 +417,19 @@ impl HasDebugSeverity for Severity {
     }
 }
 
+/// Developer warnings are only logged as [`Severity::DeveloperWarning`] when the `dev-warnings`
+/// feature is enabled, which it is by default. Without it they are logged as [`Severity::Debug`]
+/// so that production builds do not need to treat them specially.
 impl HasDeveloperWarningSeverity for Severity {
+    #[cfg(feature = "dev-warnings")]
     fn developer_warning_severity() -> Self {
         Self::DeveloperWarning
     }
+
+    #[cfg(not(feature = "dev-warnings"))]
+    fn developer_warning_severity() -> Self {
+        Self::Debug
+    }
 }
 
 impl HasInfoSeverity for Severity {
@@ -109,3 +455,218 @@ impl HasFatalSeverity for Severity {
         Self::Fatal
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_is_info() {
+        assert_eq!(Severity::default(), Severity::Info);
+    }
+
+    #[test]
+    fn debug_and_display() {
+        assert_eq!(
+            format!("{:?}", Severity::DeveloperWarning),
+            "DeveloperWarning"
+        );
+        assert_eq!(Severity::DeveloperWarning.to_string(), "dev warning");
+    }
+
+    #[test]
+    fn convenience_checks() {
+        let verbose = [
+            Severity::Trace,
+            Severity::Debug,
+            Severity::Custom(40, "chatty"),
+        ];
+        let operational = [
+            Severity::Info,
+            Severity::Warning,
+            Severity::Error,
+            Severity::Fatal,
+            Severity::Custom(140, "audit"),
+        ];
+
+        for severity in &verbose {
+            assert!(severity.is_debug_or_below(), "{:?}", severity);
+            assert!(severity.is_verbose(), "{:?}", severity);
+            assert!(!severity.is_operational(), "{:?}", severity);
+        }
+
+        for severity in &operational {
+            assert!(!severity.is_debug_or_below(), "{:?}", severity);
+            assert!(!severity.is_verbose(), "{:?}", severity);
+            assert!(severity.is_operational(), "{:?}", severity);
+        }
+
+        // Developer warnings are neither
+        assert!(!Severity::DeveloperWarning.is_verbose());
+        assert!(!Severity::DeveloperWarning.is_operational());
+    }
+
+    #[test]
+    fn str_constants() {
+        // The strum attributes need literals, so this keeps the constants from drifting
+        let names = [
+            Severity::TRACE_STR,
+            Severity::DEBUG_STR,
+            Severity::DEVELOPER_WARNING_STR,
+            Severity::INFO_STR,
+            Severity::WARNING_STR,
+            Severity::ERROR_STR,
+            Severity::FATAL_STR,
+        ];
+
+        for (severity, name) in Severity::ALL.iter().zip(names) {
+            assert_eq!(severity.as_ref(), name);
+            assert_eq!(severity.to_string(), name);
+        }
+    }
+
+    #[test]
+    fn custom() {
+        let audit = Severity::Custom(140, "audit");
+
+        assert_eq!(audit.to_string(), "audit");
+        assert_eq!(audit.as_ref(), "custom");
+        assert_eq!(format!("{:?}", audit), "Custom(140, \"audit\")");
+        assert!(audit > Severity::Info && audit < Severity::Warning);
+        assert!(audit.is_between(&Severity::min(), &Severity::max()));
+        assert_eq!(audit.as_index(), Severity::Info.as_index());
+        assert_eq!(Severity::Custom(0, "lowest").as_index(), 0);
+    }
+
+    #[test]
+    fn custom_with_same_level() {
+        assert!(Severity::Custom(120, "audit") > Severity::Info);
+        assert!(Severity::Custom(120, "notice") > Severity::Info);
+        assert!(Severity::Custom(120, "audit") < Severity::Custom(120, "notice"));
+        assert!(Severity::Custom(120, "audit") < Severity::Warning);
+        assert_ne!(Severity::Custom(120, "info"), Severity::Info);
+        assert_eq!(
+            Severity::Custom(120, "audit"),
+            Severity::Custom(120, "audit")
+        );
+    }
+
+    #[test]
+    fn custom_at_extreme_levels() {
+        let lowest = Severity::Custom(0, "all");
+        let highest = Severity::Custom(255, "zzz");
+
+        assert!(lowest > Severity::min());
+        assert!(lowest < Severity::Custom(1, "aaa"));
+        assert!(lowest < Severity::Debug);
+        assert!(highest < Severity::max());
+        assert!(highest > Severity::Custom(254, "zzz"));
+        assert!(highest > Severity::Error);
+        assert!(lowest.is_between(&Severity::min(), &Severity::max()));
+        assert!(highest.is_between(&Severity::min(), &Severity::max()));
+    }
+
+    #[test]
+    fn ordering() {
+        for pair in Severity::ALL.windows(2) {
+            assert!(pair[0] < pair[1]);
+        }
+    }
+
+    #[test]
+    fn index() {
+        for (index, severity) in Severity::ALL.iter().enumerate() {
+            assert_eq!(severity.as_index(), index);
+            assert_eq!(Severity::from_index(index).as_ref(), Some(severity));
+        }
+
+        assert_eq!(Severity::from_index(Severity::ALL.len()), None);
+    }
+
+    #[cfg(feature = "num-traits")]
+    #[test]
+    fn num_traits() {
+        use num_traits::{FromPrimitive, ToPrimitive};
+
+        assert_eq!(Severity::Warning.to_u8(), Some(4));
+        assert_eq!(Severity::from_i32(5), Some(Severity::Error));
+        assert_eq!(Severity::from_i32(-1), None);
+        assert_eq!(Severity::from_u64(7), None);
+    }
+
+    #[cfg(feature = "log-bridge")]
+    #[test]
+    fn from_log_level() {
+        assert_eq!(Severity::from_log_level(log::Level::Error), Severity::Error);
+        assert_eq!(
+            Severity::from_log_level(log::Level::Warn),
+            Severity::Warning
+        );
+        assert_eq!(Severity::from_log_level(log::Level::Info), Severity::Info);
+        assert_eq!(Severity::from_log_level(log::Level::Debug), Severity::Debug);
+        assert_eq!(Severity::from_log_level(log::Level::Trace), Severity::Trace);
+    }
+
+    #[cfg(feature = "log-bridge")]
+    #[test]
+    fn to_log_level() {
+        for level in [
+            log::Level::Error,
+            log::Level::Warn,
+            log::Level::Info,
+            log::Level::Debug,
+            log::Level::Trace,
+        ] {
+            assert_eq!(Severity::from_log_level(level).to_log_level(), Some(level));
+        }
+
+        assert_eq!(Severity::DeveloperWarning.to_log_level(), None);
+        assert_eq!(Severity::Fatal.to_log_level(), None);
+        assert_eq!(Severity::Custom(140, "audit").to_log_level(), None);
+    }
+
+    #[cfg(feature = "dev-warnings")]
+    #[test]
+    fn developer_warning_severity() {
+        assert_eq!(
+            Severity::developer_warning_severity(),
+            Severity::DeveloperWarning
+        );
+    }
+
+    #[cfg(not(feature = "dev-warnings"))]
+    #[test]
+    fn developer_warning_severity() {
+        assert_eq!(Severity::developer_warning_severity(), Severity::Debug);
+    }
+
+    #[test]
+    fn is_send_and_sync() {
+        fn assert_send_and_sync<T: Send + Sync>() {}
+
+        assert_send_and_sync::<Severity>();
+    }
+
+    #[test]
+    fn is_at_least() {
+        assert!(Severity::Warning.is_at_least(&Severity::Info));
+        assert!(Severity::Warning.is_at_least(&Severity::Warning));
+        assert!(!Severity::Warning.is_at_least(&Severity::Error));
+    }
+
+    #[test]
+    fn is_at_most() {
+        assert!(Severity::Warning.is_at_most(&Severity::Error));
+        assert!(Severity::Warning.is_at_most(&Severity::Warning));
+        assert!(!Severity::Warning.is_at_most(&Severity::Info));
+    }
+
+    #[test]
+    fn is_between() {
+        assert!(Severity::Warning.is_between(&Severity::Info, &Severity::Error));
+        assert!(Severity::Info.is_between(&Severity::Info, &Severity::Error));
+        assert!(Severity::Error.is_between(&Severity::Info, &Severity::Error));
+        assert!(!Severity::Debug.is_between(&Severity::Info, &Severity::Error));
+        assert!(!Severity::Fatal.is_between(&Severity::Info, &Severity::Error));
+    }
+}
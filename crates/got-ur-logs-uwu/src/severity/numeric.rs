@@ -0,0 +1,174 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+use std::fmt;
+
+use crate::{IsSeverity, Severity};
+
+/// A severity that is a plain number from 0 to 255, for log aggregators that represent severity
+/// as an integer.
+///
+/// Higher numbers are more critical. [`Severity`] can be converted into a numeric severity using
+/// conventional values:
+///
+/// | Severity           | Value |
+/// |--------------------|-------|
+/// | `Trace`            | 0     |
+/// | `Debug`            | 10    |
+/// | `DeveloperWarning` | 15    |
+/// | `Info`             | 20    |
+/// | `Warning`          | 30    |
+/// | `Error`            | 40    |
+/// | `Fatal`            | 50    |
+///
+/// Custom severities use the value of the most critical built-in severity whose level is not above
+/// theirs, the same as [`Severity::as_index`].
+///
+/// # Example
+///
+/// ```
+/// # use got_ur_logs_uwu::{NumericSeverity, Severity};
+/// #
+/// assert_eq!(NumericSeverity::from(Severity::Warning), NumericSeverity(30));
+/// assert_eq!(Severity::try_from(NumericSeverity(30)), Ok(Severity::Warning));
+/// assert_eq!(NumericSeverity(42).to_string(), "42");
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd)]
+pub struct NumericSeverity(pub u8);
+
+impl NumericSeverity {
+    /// The values of the built-in severities, indexed by [`Severity::as_index`].
+    const BUILT_IN_VALUES: [u8; 7] = [0, 10, 15, 20, 30, 40, 50];
+}
+
+impl fmt::Display for NumericSeverity {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}", self.0)
+    }
+}
+
+impl IsSeverity for NumericSeverity {
+    fn min() -> Self {
+        Self(u8::MIN)
+    }
+
+    fn max() -> Self {
+        Self(u8::MAX)
+    }
+}
+
+impl From<Severity> for NumericSeverity {
+    fn from(severity: Severity) -> Self {
+        Self(Self::BUILT_IN_VALUES[severity.as_index()])
+    }
+}
+
+/// Every number is a valid numeric severity, so this also provides `TryFrom<u8>`.
+impl From<u8> for NumericSeverity {
+    fn from(value: u8) -> Self {
+        Self(value)
+    }
+}
+
+impl From<NumericSeverity> for u8 {
+    fn from(severity: NumericSeverity) -> Self {
+        severity.0
+    }
+}
+
+/// Converts numeric severities back into the built-in severity with the same conventional value.
+///
+/// Numbers that do not belong to a built-in severity are returned as the error.
+impl TryFrom<NumericSeverity> for Severity {
+    type Error = NumericSeverity;
+
+    fn try_from(severity: NumericSeverity) -> Result<Self, NumericSeverity> {
+        NumericSeverity::BUILT_IN_VALUES
+            .iter()
+            .position(|value| *value == severity.0)
+            .and_then(Severity::from_index)
+            .ok_or(severity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn min_and_max() {
+        assert_eq!(NumericSeverity::min(), NumericSeverity(0));
+        assert_eq!(NumericSeverity::max(), NumericSeverity(255));
+    }
+
+    #[test]
+    fn ordering() {
+        assert!(NumericSeverity(10) < NumericSeverity(20));
+        assert!(NumericSeverity(20).is_at_least(&NumericSeverity(20)));
+        assert!(!NumericSeverity(19).is_at_least(&NumericSeverity(20)));
+
+        let values = Severity::ALL
+            .iter()
+            .cloned()
+            .map(NumericSeverity::from)
+            .collect::<Vec<_>>();
+
+        assert!(values.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(NumericSeverity(0).to_string(), "0");
+        assert_eq!(NumericSeverity(255).to_string(), "255");
+    }
+
+    #[test]
+    fn from_severity() {
+        assert_eq!(NumericSeverity::from(Severity::Trace), NumericSeverity(0));
+        assert_eq!(NumericSeverity::from(Severity::Debug), NumericSeverity(10));
+        assert_eq!(NumericSeverity::from(Severity::Info), NumericSeverity(20));
+        assert_eq!(
+            NumericSeverity::from(Severity::Warning),
+            NumericSeverity(30)
+        );
+        assert_eq!(NumericSeverity::from(Severity::Error), NumericSeverity(40));
+        assert_eq!(NumericSeverity::from(Severity::Fatal), NumericSeverity(50));
+        assert_eq!(
+            NumericSeverity::from(Severity::Custom(140, "audit")),
+            NumericSeverity(20)
+        );
+    }
+
+    #[test]
+    fn from_u8() {
+        assert_eq!(NumericSeverity::from(42), NumericSeverity(42));
+        assert_eq!(u8::from(NumericSeverity(42)), 42);
+    }
+
+    #[test]
+    fn round_trip() {
+        for severity in Severity::ALL {
+            assert_eq!(
+                Severity::try_from(NumericSeverity::from(severity.clone())),
+                Ok(severity)
+            );
+        }
+
+        assert_eq!(
+            Severity::try_from(NumericSeverity(42)),
+            Err(NumericSeverity(42))
+        );
+    }
+}
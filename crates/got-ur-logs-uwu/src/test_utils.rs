@@ -0,0 +1,67 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, SystemTime},
+};
+
+use crate::Clock;
+
+/// Held by tests that write directly to the console so that they do not interfere with each
+/// other's redirected output.
+static CONSOLE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Locks the console for the duration of a test.
+pub fn lock_console() -> MutexGuard<'static, ()> {
+    // A test that panicked while holding the lock does not affect the console
+    CONSOLE_LOCK
+        .lock()
+        .unwrap_or_else(|error| error.into_inner())
+}
+
+/// A clock that only moves when told to.
+///
+/// Clones share the same time, so a test can keep a clone to move a clock it has handed off.
+#[derive(Clone)]
+pub struct FakeClock {
+    now: Arc<Mutex<SystemTime>>,
+}
+
+impl FakeClock {
+    /// Creates a clock that is stopped at the given time.
+    pub fn at(now: SystemTime) -> Self {
+        Self {
+            now: Arc::new(Mutex::new(now)),
+        }
+    }
+
+    /// Moves the clock forward.
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+}
+
+impl Default for FakeClock {
+    fn default() -> Self {
+        Self::at(SystemTime::UNIX_EPOCH)
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> SystemTime {
+        *self.now.lock().unwrap()
+    }
+}
@@ -0,0 +1,374 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+//! Utilities for making assertions about logged messages in tests.
+//!
+//! Requires the `testing` feature.
+
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    writers::CaptureWriter, HasSeverity, HasText, IsSeverity, Logger, Message, Severity,
+    WriterHandle,
+};
+
+/// Held while the global logger's messages are being captured so that captures do not overlap.
+static GLOBAL_CAPTURE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Removes a writer from the global logger when dropped, even if the captured function panics.
+struct GlobalWriterGuard {
+    handle: WriterHandle,
+}
+
+impl Drop for GlobalWriterGuard {
+    fn drop(&mut self) {
+        Logger::<Severity, Message<Severity>>::global_mut().remove_writer(self.handle);
+    }
+}
+
+/// Runs a function with a new logger that captures all of its messages.
+///
+/// The logger has no writers other than the capture writer, and its minimum severity is the
+/// lowest severity so that all messages are captured.
+///
+/// # Arguments
+///
+/// * `function` - The function to run with the logger
+///
+/// # Returns
+///
+/// Returns what `function` returns along with all of the messages that were captured.
+///
+/// # Example
+///
+/// ```
+/// # use got_ur_logs_uwu::{testing::{assert_logged, with_captured_logs}, Severity};
+/// #
+/// let (_, messages) = with_captured_logs::<Severity, _>(|logger| {
+///     logger.log_info("hello, world");
+/// });
+///
+/// assert_logged(&messages, &Severity::Info, "hello");
+/// ```
+pub fn with_captured_logs<SeverityType: 'static + IsSeverity + Clone, ReturnType>(
+    function: impl FnOnce(&mut Logger<SeverityType, Message<SeverityType>>) -> ReturnType,
+) -> (ReturnType, Vec<Message<SeverityType>>) {
+    let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+    let mut logger = Logger::default();
+
+    logger.add_writer_shared(writer.clone());
+
+    let result = function(&mut logger);
+
+    let messages = writer.lock().unwrap().messages();
+
+    (result, messages)
+}
+
+/// Something that holds captured messages which can be asserted on.
+///
+/// This is implemented for [`CaptureWriter`] and shared handles to it, so the assertions in this
+/// module can be given the same writer that was added to a logger. It is also implemented for lists
+/// of messages, like those returned by [`with_captured_logs`] and [`capture_global_logs`].
+pub trait CapturedMessages<SeverityType: IsSeverity> {
+    /// Gets a snapshot of the captured messages.
+    fn captured_messages(&self) -> Vec<Message<SeverityType>>;
+}
+
+impl<SeverityType: IsSeverity + Clone> CapturedMessages<SeverityType>
+    for [Message<SeverityType>]
+{
+    fn captured_messages(&self) -> Vec<Message<SeverityType>> {
+        self.to_vec()
+    }
+}
+
+impl<SeverityType: IsSeverity + Clone> CapturedMessages<SeverityType>
+    for Vec<Message<SeverityType>>
+{
+    fn captured_messages(&self) -> Vec<Message<SeverityType>> {
+        self.clone()
+    }
+}
+
+impl<SeverityType: IsSeverity + Clone> CapturedMessages<SeverityType>
+    for CaptureWriter<SeverityType>
+{
+    fn captured_messages(&self) -> Vec<Message<SeverityType>> {
+        self.messages()
+    }
+}
+
+impl<SeverityType: IsSeverity + Clone> CapturedMessages<SeverityType>
+    for Mutex<CaptureWriter<SeverityType>>
+{
+    fn captured_messages(&self) -> Vec<Message<SeverityType>> {
+        // A panic while the writer was locked does not corrupt the messages it already captured
+        self.lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .messages()
+    }
+}
+
+impl<SeverityType: IsSeverity, CapturedType: CapturedMessages<SeverityType> + ?Sized>
+    CapturedMessages<SeverityType> for Arc<CapturedType>
+{
+    fn captured_messages(&self) -> Vec<Message<SeverityType>> {
+        self.as_ref().captured_messages()
+    }
+}
+
+/// Describes captured messages for assertion failures.
+fn describe_messages<SeverityType: IsSeverity>(messages: &[Message<SeverityType>]) -> String {
+    if messages.is_empty() {
+        return "no messages were captured".to_owned();
+    }
+
+    messages
+        .iter()
+        .map(|message| format!("  {}", message))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Runs a function while capturing the messages logged to the global logger.
+///
+/// This is useful for testing code that logs with the macros. A capture writer is added to the
+/// global logger for the duration of `function` and removed afterwards. Calls from multiple tests
+/// are serialized so that each only captures while it is running, but messages logged to the
+/// global logger from other threads during that time are captured too.
+///
+/// # Arguments
+///
+/// * `function` - The function to run
+///
+/// # Returns
+///
+/// Returns what `function` returns along with all of the messages that were captured.
+///
+/// # Example
+///
+/// ```
+/// # use got_ur_logs_uwu::{log_info, testing::{assert_logged, capture_global_logs}, Severity};
+/// #
+/// let (_, messages) = capture_global_logs(|| {
+///     log_info!("hello, world");
+/// });
+///
+/// assert_logged(&messages, &Severity::Info, "hello");
+/// ```
+pub fn capture_global_logs<ReturnType>(
+    function: impl FnOnce() -> ReturnType,
+) -> (ReturnType, Vec<Message<Severity>>) {
+    // A capture that panicked has already removed its writer, so the lock is still usable
+    let _lock = GLOBAL_CAPTURE_LOCK
+        .lock()
+        .unwrap_or_else(|error| error.into_inner());
+
+    let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+    let guard = GlobalWriterGuard {
+        handle: Logger::<Severity, Message<Severity>>::global_mut()
+            .add_writer_shared(writer.clone()),
+    };
+
+    let result = function();
+
+    drop(guard);
+
+    let messages = writer.lock().unwrap().messages();
+
+    (result, messages)
+}
+
+/// Asserts that a message with the given severity and containing the given text was logged.
+///
+/// # Arguments
+///
+/// * `captured` - The capture writer that was added to the logger, or the messages it captured
+/// * `severity` - The severity the message must have
+/// * `text_substr` - Text that the message must contain
+///
+/// # Panics
+///
+/// Panics with a list of the captured messages if none of them match.
+///
+/// # Example
+///
+/// ```
+/// # use std::sync::{Arc, Mutex};
+/// # use got_ur_logs_uwu::{testing::assert_logged, writers::CaptureWriter, Logger, Message, Severity};
+/// #
+/// let mut logger = Logger::<Severity, Message<Severity>>::default();
+///
+/// let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+///
+/// logger.add_writer_shared(writer.clone());
+///
+/// logger.log_info("hello, world");
+///
+/// assert_logged(&writer, &Severity::Info, "hello");
+/// ```
+#[track_caller]
+pub fn assert_logged<SeverityType: IsSeverity>(
+    captured: &(impl CapturedMessages<SeverityType> + ?Sized),
+    severity: &SeverityType,
+    text_substr: &str,
+) {
+    let messages = captured.captured_messages();
+
+    let found = messages
+        .iter()
+        .any(|message| message.severity() == severity && message.text().contains(text_substr));
+
+    if !found {
+        panic!(
+            "expected a message with severity {} containing {:?} to be logged, but found:\n{}",
+            severity,
+            text_substr,
+            describe_messages(&messages)
+        );
+    }
+}
+
+/// Asserts that no message with the given severity was logged.
+///
+/// # Arguments
+///
+/// * `captured` - The capture writer that was added to the logger, or the messages it captured
+/// * `severity` - The severity that no message may have
+///
+/// # Panics
+///
+/// Panics with a list of the captured messages if any of them have the severity.
+///
+/// # Example
+///
+/// ```
+/// # use std::sync::{Arc, Mutex};
+/// # use got_ur_logs_uwu::{
+/// #     testing::assert_not_logged, writers::CaptureWriter, Logger, Message, Severity
+/// # };
+/// #
+/// let mut logger = Logger::<Severity, Message<Severity>>::default();
+///
+/// let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+///
+/// logger.add_writer_shared(writer.clone());
+///
+/// logger.log_info("hello, world");
+///
+/// assert_not_logged(&writer, &Severity::Error);
+/// ```
+#[track_caller]
+pub fn assert_not_logged<SeverityType: IsSeverity>(
+    captured: &(impl CapturedMessages<SeverityType> + ?Sized),
+    severity: &SeverityType,
+) {
+    let messages = captured.captured_messages();
+
+    if messages
+        .iter()
+        .any(|message| message.severity() == severity)
+    {
+        panic!(
+            "expected no messages with severity {} to be logged, but found:\n{}",
+            severity,
+            describe_messages(&messages)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FromCoreFields, Severity};
+
+    use super::*;
+
+    fn messages() -> Vec<Message<Severity>> {
+        vec![
+            Message::from_core_fields(Severity::Info, "hello, world"),
+            Message::from_core_fields(Severity::Warning, "uh oh"),
+        ]
+    }
+
+    #[test]
+    fn captured_logs() {
+        let (result, messages) = with_captured_logs::<Severity, _>(|logger| {
+            logger.log_debug("first");
+            logger.log_error("second");
+
+            42
+        });
+
+        assert_eq!(result, 42);
+        assert_eq!(messages.len(), 2);
+        assert_logged(&messages, &Severity::Debug, "first");
+        assert_logged(&messages, &Severity::Error, "second");
+    }
+
+    #[test]
+    fn captured_global_logs() {
+        let (_, messages) = capture_global_logs(|| {
+            crate::log_warning!("captured");
+        });
+
+        assert_logged(&messages, &Severity::Warning, "captured");
+    }
+
+    #[test]
+    fn logged() {
+        assert_logged(&messages(), &Severity::Info, "world");
+        assert_logged(&messages(), &Severity::Warning, "");
+    }
+
+    #[test]
+    #[should_panic(expected = "expected a message with severity error containing \"uh oh\"")]
+    fn logged_wrong_severity() {
+        assert_logged(&messages(), &Severity::Error, "uh oh");
+    }
+
+    #[test]
+    #[should_panic(expected = "no messages were captured")]
+    fn logged_empty() {
+        assert_logged(&CaptureWriter::<Severity>::new(), &Severity::Info, "hello");
+    }
+
+    #[test]
+    fn logged_to_logger() {
+        let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+        let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+        logger.add_writer_shared(writer.clone());
+
+        logger.log_error("uh oh");
+
+        assert_logged(&writer, &Severity::Error, "uh");
+        assert_not_logged(&writer, &Severity::Info);
+    }
+
+    #[test]
+    fn not_logged() {
+        assert_not_logged(&messages(), &Severity::Error);
+    }
+
+    #[test]
+    #[should_panic(expected = "[warning] uh oh")]
+    fn not_logged_found() {
+        assert_not_logged(&messages(), &Severity::Warning);
+    }
+}
@@ -15,13 +15,18 @@
 
 use crate::Result;
 use mockall::automock;
-use std::{fmt::Display, io};
+#[cfg(feature = "tokio")]
+use std::future::Future;
+use std::{any, fmt::Display, io, time::SystemTime};
 
 #[allow(unused_imports)]
-use crate::Severity; // Used for doc comments
+use crate::{private::MessageBuilder, Severity}; // Used for doc comments
 
 /// A trait implemented by all severity types.
-pub trait IsSeverity: PartialEq + PartialOrd + Display {
+///
+/// Severity types must be [`Send`] and [`Sync`] so that messages, and loggers that handle them,
+/// can be shared with writers that run on other threads.
+pub trait IsSeverity: PartialEq + PartialOrd + Display + Send + Sync {
     /// Get the minimum severity.
     ///
     /// This is the most verbose. By default this is a [`Severity::Trace`] message.
@@ -31,6 +36,34 @@ pub trait IsSeverity: PartialEq + PartialOrd + Display {
     ///
     /// This is the most critical. By default this is a [`Severity::Fatal`] message.
     fn max() -> Self;
+
+    /// Checks whether this severity is at least as critical as a threshold.
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - The least critical severity that is accepted
+    fn is_at_least(&self, threshold: &Self) -> bool {
+        self >= threshold
+    }
+
+    /// Checks whether this severity is at most as critical as a threshold.
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - The most critical severity that is accepted
+    fn is_at_most(&self, threshold: &Self) -> bool {
+        self <= threshold
+    }
+
+    /// Checks whether this severity is within an inclusive range.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - The least critical severity that is accepted
+    /// * `max` - The most critical severity that is accepted
+    fn is_between(&self, min: &Self, max: &Self) -> bool {
+        self.is_at_least(min) && self.is_at_most(max)
+    }
 }
 
 /// A trait implemented by severity types that have a trace level.
@@ -81,11 +114,86 @@ pub trait HasSeverity<Severity: IsSeverity> {
     fn severity(&self) -> &Severity;
 }
 
+/// A trait implemented by message types whose severity can be changed after construction.
+pub trait HasSeverityMut<Severity: IsSeverity>: HasSeverity<Severity> {
+    /// Get mutable access to the severity of the message.
+    fn severity_mut(&mut self) -> &mut Severity;
+}
+
 /// A trait implemented by all message types, indicating that they have text content.
 pub trait HasText {
     fn text(&self) -> &str;
 }
 
+/// A trait implemented by message types whose text content can be changed after construction.
+pub trait HasTextMut: HasText {
+    /// Get mutable access to the text content of the message.
+    fn text_mut(&mut self) -> &mut String;
+}
+
+/// A trait implemented by all types that have both a severity and text content.
+///
+/// It is implemented automatically and exists so that messages can be used as trait objects, like
+/// `&dyn HasCoreFields<Severity>`.
+pub trait HasCoreFields<Severity: IsSeverity>: HasSeverity<Severity> + HasText {}
+
+impl<Severity: IsSeverity, MessageType: HasSeverity<Severity> + HasText + ?Sized>
+    HasCoreFields<Severity> for MessageType
+{
+}
+
+/// A trait implemented by message types that can be tagged for categorization.
+pub trait HasTags {
+    /// Get the tags of the message.
+    fn tags(&self) -> &[String];
+}
+
+/// A trait implemented by message types that can carry a correlation ID.
+///
+/// Correlation IDs are used to associate messages that belong to the same operation, such as all
+/// the messages logged while handling a single request.
+pub trait HasCorrelationId {
+    /// Get the correlation ID of the message, if it has one.
+    fn correlation_id(&self) -> Option<&str>;
+}
+
+/// A trait implemented by message types that record when they were emitted.
+pub trait HasTimestamp {
+    /// Get the time at which the message was emitted.
+    fn timestamp(&self) -> SystemTime;
+}
+
+/// A trait implemented by message types that can record where in the code they were logged.
+///
+/// The logging macros fill this in automatically.
+pub trait HasSourceLocation {
+    /// Get the path of the source file the message was logged from, if it is known.
+    fn source_file(&self) -> Option<&'static str>;
+
+    /// Get the line number the message was logged from, if it is known.
+    fn source_line(&self) -> Option<u32>;
+
+    /// Get the path of the module the message was logged from, if it is known.
+    fn source_module(&self) -> Option<&'static str>;
+}
+
+/// A trait implemented by message types that have a unique ID.
+///
+/// IDs can be used to deduplicate, correlate, or replay messages.
+pub trait HasId {
+    /// Get the ID of the message.
+    fn id(&self) -> u64;
+}
+
+/// A trait implemented by message types that carry structured key-value fields.
+pub trait HasFields {
+    /// Get the structured fields of the message as key-value pairs.
+    fn fields(&self) -> &[(String, String)];
+
+    /// Get mutable access to the structured fields of the message so that they can be added to.
+    fn fields_mut(&mut self) -> &mut Vec<(String, String)>;
+}
+
 /// A trait implemented by message types so that they can be constructed by macros.
 ///
 /// It is essentially a constructor for the message object which is provided with just the core
@@ -98,11 +206,83 @@ pub trait FromCoreFields<Severity: IsSeverity> {
     /// * `severity` - The severity of the message
     /// * `text` - The text content of the message
     fn from_core_fields(severity: Severity, text: &str) -> Self;
+
+    /// Construct a new message from the core fields, emitted at the given time.
+    ///
+    /// Loggers use this so that messages are stamped by their [`Clock`](crate::Clock). By default
+    /// the timestamp is ignored, which is correct for message types without one.
+    ///
+    /// # Arguments
+    ///
+    /// * `severity` - The severity of the message
+    /// * `text` - The text content of the message
+    /// * `timestamp` - When the message was emitted
+    fn from_core_fields_at(severity: Severity, text: &str, _timestamp: SystemTime) -> Self
+    where
+        Self: Sized,
+    {
+        Self::from_core_fields(severity, text)
+    }
+}
+
+/// A trait implemented by builders that can construct a message type.
+///
+/// The [`log_message!`](crate::log_message) macro fills in a [`MessageBuilder`] from its key-value
+/// pairs and then calls this to get the message to log. Implementing it for other message types
+/// lets the macros log custom messages to loggers that accept them.
+///
+/// # Example
+///
+/// ```
+/// # use got_ur_logs_uwu::{
+/// #     private::MessageBuilder,
+/// #     BuildMessage,
+/// #     HasSeverity,
+/// #     HasText,
+/// #     Logger,
+/// #     Severity,
+/// #     log_message,
+/// # };
+/// #
+/// struct CustomMessage {
+///     severity: Severity,
+///     text: String,
+/// }
+///
+/// impl HasSeverity<Severity> for CustomMessage {
+///     fn severity(&self) -> &Severity {
+///         &self.severity
+///     }
+/// }
+///
+/// impl HasText for CustomMessage {
+///     fn text(&self) -> &str {
+///         &self.text
+///     }
+/// }
+///
+/// impl BuildMessage<CustomMessage> for MessageBuilder<Severity> {
+///     fn build(self) -> CustomMessage {
+///         CustomMessage {
+///             severity: self.severity.expect("severity must be set"),
+///             text: self.text.expect("text must be set"),
+///         }
+///     }
+/// }
+///
+/// let logger = Logger::<Severity, CustomMessage>::default();
+///
+/// log_message!(logger = logger, severity = Severity::Info, text = "hello, world");
+/// ```
+pub trait BuildMessage<Message> {
+    /// Builds the message.
+    fn build(self) -> Message;
 }
 
 /// A trait implemented by all log writers.
 ///
-/// Allows them to accept messages so that they may be written.
+/// Allows them to accept messages so that they may be written. Writers that are added to a
+/// [`crate::Logger`] must also be [`Send`], since loggers can be shared between threads.
 #[automock]
 pub trait Write<Severity: IsSeverity, Message: HasSeverity<Severity> + HasText> {
     /// Writes a given message.
@@ -115,8 +295,145 @@ pub trait Write<Severity: IsSeverity, Message: HasSeverity<Severity> + HasText>
     ///
     /// Returns `Ok(())` if the message was successfully written, or an error if it was not.
     fn write(&mut self, message: &Message) -> Result<()>;
+
+    /// Writes out any messages that the writer has buffered.
+    ///
+    /// By default this does nothing, which is correct for writers that do not buffer.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the buffered messages were successfully written, or an error if they
+    /// were not.
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Gets a short identifier for the writer, like `"console"`, for diagnostics and configuration
+    /// validation.
+    ///
+    /// By default this is the full type name of the writer.
+    fn name(&self) -> &'static str {
+        any::type_name::<Self>()
+    }
 }
 
+/// A trait implemented by log writers that write without blocking the async executor.
+///
+/// This is the async counterpart of [`Write`] and requires the `tokio` feature.
+#[cfg(feature = "tokio")]
+pub trait AsyncWrite<Severity: IsSeverity, Message: HasSeverity<Severity> + HasText> {
+    /// Writes a given message.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message to write
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the message was successfully written, or an error if it was not.
+    fn write(&mut self, message: &Message) -> impl Future<Output = Result<()>> + Send;
+
+    /// Writes out any messages that the writer has buffered.
+    ///
+    /// By default this does nothing, which is correct for writers that do not buffer.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the buffered messages were successfully written, or an error if they
+    /// were not.
+    fn flush(&mut self) -> impl Future<Output = Result<()>> + Send {
+        async { Ok(()) }
+    }
+}
+
+/// A trait implemented by all log formatters.
+///
+/// Formatters are used by writers to turn messages into output.
 pub trait Format<Severity: IsSeverity, Message: HasSeverity<Severity> + HasText> {
+    /// Formats a given message.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message to format
+    /// * `writer` - Where to write the formatted message
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the message was successfully formatted, or an error if it was not.
     fn format(&mut self, message: &Message, writer: &mut dyn io::Write) -> Result<()>;
+
+    /// Formats a given message to a writer whose type is known at compile time.
+    ///
+    /// Writers that know the type of their formatter should prefer this over [`Format::format`].
+    /// By default it calls [`Format::format`], but formatters can override it so that writing to
+    /// the writer is monomorphized and can be inlined instead of going through dynamic dispatch.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message to format
+    /// * `writer` - Where to write the formatted message
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the message was successfully formatted, or an error if it was not.
+    fn format_generic<WriterType: io::Write>(
+        &mut self,
+        message: &Message,
+        writer: &mut WriterType,
+    ) -> Result<()>
+    where
+        Self: Sized,
+    {
+        self.format(message, writer)
+    }
+
+    /// Formats only the metadata of a given message, such as its severity and tags.
+    ///
+    /// Some log shippers, like Elasticsearch, need the metadata to be serialized separately from
+    /// the full message so that it can be indexed. By default this is an empty JSON object, `{}`.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message whose metadata to format
+    ///
+    /// # Returns
+    ///
+    /// Returns the serialized metadata, or an error if it could not be formatted.
+    fn format_metadata(&mut self, _message: &Message) -> Result<String> {
+        Ok("{}".to_owned())
+    }
+
+    /// Writes out anything the formatter has buffered instead of writing it in
+    /// [`Format::format`].
+    ///
+    /// Writers call this when they are flushed. By default this does nothing, which is correct
+    /// for formatters that do not buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Where to write the buffered output
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the buffered output was successfully written, or an error if it was
+    /// not.
+    fn flush(&mut self, _writer: &mut dyn io::Write) -> Result<()> {
+        Ok(())
+    }
+
+    /// Checks whether the formatter outputs ANSI color codes.
+    ///
+    /// Writers can use this to decide whether the formatter is suitable for their destination.
+    /// By default this is `false`.
+    fn supports_color(&self) -> bool {
+        false
+    }
+
+    /// Gets a short identifier for the formatter, like `"plaintext"`, for diagnostics and
+    /// serialized configuration.
+    ///
+    /// By default this is the full type name of the formatter.
+    fn name(&self) -> &'static str {
+        any::type_name::<Self>()
+    }
 }
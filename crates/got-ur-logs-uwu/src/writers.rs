@@ -13,6 +13,38 @@
 // You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
 // not, see <https://www.gnu.org/licenses/>.
 
+#[cfg(feature = "tokio")]
+mod async_file;
+mod capture;
 mod console;
+mod daily_rolling_file;
+mod file;
+mod gelf_udp;
+mod jsonl_file;
+#[cfg(feature = "mmap")]
+mod mmap;
+mod ring_buffer;
 
-pub use console::ConsoleWriter;
+#[cfg(feature = "tokio")]
+pub use async_file::AsyncFileWriter;
+pub use capture::CaptureWriter;
+pub use console::{ConsoleWriter, ConsoleWriterDestination, SeverityRange};
+pub use daily_rolling_file::DailyRollingFileWriter;
+pub use file::FileWriter;
+pub use gelf_udp::{CompressionType, GelfUdpWriter};
+pub use jsonl_file::JsonlFileWriter;
+#[cfg(feature = "mmap")]
+pub use mmap::{MmapRolloverPolicy, MmapWriter};
+pub use ring_buffer::RingBufferWriter;
+
+/// Ends a formatted message with a newline so that each message is on its own line.
+///
+/// Nothing is added to an empty buffer, since formatters that batch their output, like
+/// [`crate::formatters::Json::batch_mode`], do not write anything until they are flushed. Nothing is
+/// added either if the formatter already ended the message with a newline, like
+/// [`crate::formatters::Json`] does.
+pub(crate) fn end_line(buffer: &mut Vec<u8>) {
+    if buffer.last().is_some_and(|last| *last != b'\n') {
+        buffer.push(b'\n');
+    }
+}
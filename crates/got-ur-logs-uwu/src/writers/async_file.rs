@@ -0,0 +1,189 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    traits::{AsyncWrite, Format, HasSeverity, HasText},
+    writers::end_line,
+    IsSeverity, Result,
+};
+use std::{
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+use tokio::{
+    fs::{File, OpenOptions},
+    io::AsyncWriteExt,
+};
+
+/// A writer that appends messages to a file without blocking the async executor.
+///
+/// This is the async counterpart of [`FileWriter`](crate::writers::FileWriter) and requires the
+/// `tokio` feature. Each message is followed by a newline, and the file is flushed after every
+/// message so that logs are not lost if the program crashes.
+///
+/// # Example
+///
+/// ```no_run
+/// # use got_ur_logs_uwu::{
+/// #     writers::AsyncFileWriter,
+/// #     formatters::Plaintext,
+/// #     AsyncWrite,
+/// #     FromCoreFields,
+/// #     Message,
+/// #     Severity,
+/// # };
+/// #
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> got_ur_logs_uwu::Result<()> {
+/// let mut writer = AsyncFileWriter::open("app.log", Plaintext::new_default()).await?;
+///
+/// writer
+///     .write(&Message::from_core_fields(Severity::Info, "hello, world"))
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct AsyncFileWriter<
+    SeverityType: IsSeverity,
+    MessageType: HasSeverity<SeverityType> + HasText,
+    FormatterType: Format<SeverityType, MessageType>,
+> {
+    path: PathBuf,
+    file: File,
+    formatter: FormatterType,
+    severity_type_phantom: PhantomData<SeverityType>,
+    message_type_phantom: PhantomData<MessageType>,
+}
+
+impl<
+        SeverityType: IsSeverity,
+        MessageType: HasSeverity<SeverityType> + HasText,
+        FormatterType: Format<SeverityType, MessageType>,
+    > AsyncFileWriter<SeverityType, MessageType, FormatterType>
+{
+    /// Opens an async file writer.
+    ///
+    /// The file is created if it does not exist, and appended to if it does.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the log file, whose directory must already exist
+    /// * `formatter` - The formatter to use for messages
+    ///
+    /// # Returns
+    ///
+    /// Returns the writer, or an error if the file could not be opened for writing.
+    pub async fn open<PathType: AsRef<Path>>(
+        path: PathType,
+        formatter: FormatterType,
+    ) -> Result<Self> {
+        let path = path.as_ref().to_owned();
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .await?;
+
+        Ok(Self {
+            path,
+            file,
+            formatter,
+            severity_type_phantom: PhantomData,
+            message_type_phantom: PhantomData,
+        })
+    }
+
+    /// Gets the path of the log file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl<
+        SeverityType: IsSeverity,
+        MessageType: HasSeverity<SeverityType> + HasText + Send + Sync,
+        FormatterType: Format<SeverityType, MessageType> + Send,
+    > AsyncWrite<SeverityType, MessageType>
+    for AsyncFileWriter<SeverityType, MessageType, FormatterType>
+{
+    async fn write(&mut self, message: &MessageType) -> Result<()> {
+        // Formatting is synchronous, so the message is formatted into a buffer before any I/O
+        let mut buffer = Vec::new();
+
+        self.formatter.format_generic(message, &mut buffer)?;
+        end_line(&mut buffer);
+
+        self.file.write_all(&buffer).await?;
+        self.file.flush().await?;
+
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        let mut buffer = Vec::new();
+
+        self.formatter.flush(&mut buffer)?;
+        end_line(&mut buffer);
+
+        self.file.write_all(&buffer).await?;
+        self.file.flush().await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{formatters::Plaintext, FromCoreFields, Message, Severity};
+    use std::{env, fs, process};
+
+    #[tokio::test]
+    async fn writes_messages() -> Result<()> {
+        let path =
+            env::temp_dir().join(format!("got-ur-logs-uwu-async-file-{}.log", process::id()));
+
+        fs::write(&path, "existing\n")?;
+
+        let mut writer = AsyncFileWriter::open(&path, Plaintext::new_default()).await?;
+
+        writer
+            .write(&Message::from_core_fields(Severity::Info, "first"))
+            .await?;
+        writer
+            .write(&Message::from_core_fields(Severity::Error, "second"))
+            .await?;
+        writer.flush().await?;
+
+        assert_eq!(
+            fs::read_to_string(writer.path())?,
+            "existing\n[info] first\n[error] second\n"
+        );
+
+        fs::remove_file(&path)?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn missing_directory() {
+        assert!(AsyncFileWriter::<Severity, Message<Severity>, _>::open(
+            env::temp_dir().join("got-ur-logs-uwu-missing-directory/nested/app.log"),
+            Plaintext::new_default(),
+        )
+        .await
+        .is_err());
+    }
+}
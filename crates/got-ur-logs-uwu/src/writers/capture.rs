@@ -0,0 +1,178 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+use std::marker::PhantomData;
+
+use crate::{HasSeverity, HasText, IsSeverity, Message, Result, Write};
+
+/// A writer that captures messages in memory.
+///
+/// This is mostly useful for making assertions about logged messages in tests. It captures
+/// [`Message`]s by default, but works with any message type that can be cloned.
+///
+/// # Example
+///
+/// ```
+/// # use std::sync::{Arc, Mutex};
+/// # use got_ur_logs_uwu::{writers::CaptureWriter, HasText, Logger, Message, Severity};
+/// #
+/// let mut logger = Logger::<Severity, Message<Severity>>::default();
+///
+/// let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+///
+/// logger.add_writer_shared(writer.clone());
+///
+/// logger.log_info("hello, world");
+///
+/// let writer = writer.lock().unwrap();
+///
+/// assert_eq!(writer.message_count(), 1);
+/// assert_eq!(writer.last_message().unwrap().text(), "hello, world");
+/// ```
+pub struct CaptureWriter<
+    SeverityType: IsSeverity + Clone,
+    MessageType: HasSeverity<SeverityType> + HasText + Clone = Message<SeverityType>,
+> {
+    messages: Vec<MessageType>,
+    severity_type_phantom: PhantomData<SeverityType>,
+}
+
+impl<
+        SeverityType: IsSeverity + Clone,
+        MessageType: HasSeverity<SeverityType> + HasText + Clone,
+    > CaptureWriter<SeverityType, MessageType>
+{
+    /// Create a new capture writer with no messages.
+    pub fn new() -> Self {
+        Self {
+            messages: Vec::new(),
+            severity_type_phantom: PhantomData,
+        }
+    }
+
+    /// Gets a snapshot of all the messages that have been captured so far.
+    pub fn messages(&self) -> Vec<MessageType> {
+        self.messages.clone()
+    }
+
+    /// Gets the most recently captured message, if any.
+    pub fn last_message(&self) -> Option<MessageType> {
+        self.messages.last().cloned()
+    }
+
+    /// Gets the number of messages that have been captured so far.
+    pub fn message_count(&self) -> usize {
+        self.messages.len()
+    }
+}
+
+// This is implemented manually because deriving it would require `MessageType: Default`
+impl<
+        SeverityType: IsSeverity + Clone,
+        MessageType: HasSeverity<SeverityType> + HasText + Clone,
+    > Default for CaptureWriter<SeverityType, MessageType>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<
+        SeverityType: IsSeverity + Clone,
+        MessageType: HasSeverity<SeverityType> + HasText + Clone,
+    > Write<SeverityType, MessageType> for CaptureWriter<SeverityType, MessageType>
+{
+    fn write(&mut self, message: &MessageType) -> Result<()> {
+        self.messages.push(message.clone());
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "capture"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FromCoreFields, Severity};
+
+    #[test]
+    fn empty() {
+        let writer = CaptureWriter::<Severity>::new();
+
+        assert_eq!(writer.message_count(), 0);
+        assert!(writer.messages().is_empty());
+        assert!(writer.last_message().is_none());
+    }
+
+    #[test]
+    fn captures_messages() -> Result<()> {
+        let mut writer = CaptureWriter::new();
+
+        writer.write(&Message::from_core_fields(Severity::Info, "first"))?;
+        writer.write(&Message::from_core_fields(Severity::Error, "second"))?;
+
+        let messages = writer.messages();
+
+        assert_eq!(writer.message_count(), 2);
+        assert_eq!(messages[0].text(), "first");
+        assert_eq!(*messages[1].severity(), Severity::Error);
+
+        let last_message = writer.last_message().unwrap();
+
+        assert_eq!(last_message.text(), "second");
+
+        Ok(())
+    }
+
+    #[test]
+    fn custom_message_type() -> Result<()> {
+        #[derive(Clone)]
+        struct CustomMessage {
+            severity: Severity,
+            text: String,
+        }
+
+        impl HasSeverity<Severity> for CustomMessage {
+            fn severity(&self) -> &Severity {
+                &self.severity
+            }
+        }
+
+        impl HasText for CustomMessage {
+            fn text(&self) -> &str {
+                &self.text
+            }
+        }
+
+        let mut writer = CaptureWriter::<Severity, CustomMessage>::new();
+
+        writer.write(&CustomMessage {
+            severity: Severity::Info,
+            text: "hello, world".to_owned(),
+        })?;
+
+        assert_eq!(writer.message_count(), 1);
+        assert_eq!(writer.last_message().unwrap().text(), "hello, world");
+
+        Ok(())
+    }
+
+    #[test]
+    fn name() {
+        assert_eq!(CaptureWriter::<Severity>::new().name(), "capture");
+    }
+}
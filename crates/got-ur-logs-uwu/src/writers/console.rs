@@ -15,14 +15,70 @@
 
 use crate::{
     traits::{Format, HasSeverity, HasText},
-    IsSeverity, Result, Write,
+    Error, IsSeverity, Result, Write,
+};
+use std::{
+    any::Any,
+    io::{self, Write as _},
+    marker::PhantomData,
+    panic::{self, AssertUnwindSafe},
 };
-use std::{io, marker::PhantomData};
 
-enum ConsoleWriterDestination<'writer> {
+/// Where a [`ConsoleWriter`] writes messages to.
+pub enum ConsoleWriterDestination<'writer> {
+    /// Standard output.
     Stdout,
+    /// Standard error.
     Stderr,
-    Writer(&'writer mut dyn io::Write),
+    /// A custom writer, see [`ConsoleWriter::new_write`].
+    Writer(&'writer mut (dyn io::Write + Send)),
+}
+
+/// An inclusive range of severities, used to choose where [`ConsoleWriter::new_per_severity`]
+/// writes messages to.
+///
+/// # Example
+///
+/// ```
+/// # use got_ur_logs_uwu::{writers::SeverityRange, Severity};
+/// #
+/// let range = SeverityRange::new(Severity::Warning, Severity::Fatal);
+///
+/// assert!(range.contains(&Severity::Error));
+/// assert!(!range.contains(&Severity::Info));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SeverityRange<SeverityType: IsSeverity> {
+    /// The least critical severity in the range.
+    pub min: SeverityType,
+    /// The most critical severity in the range.
+    pub max: SeverityType,
+}
+
+impl<SeverityType: IsSeverity> SeverityRange<SeverityType> {
+    /// Creates a new severity range.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - The least critical severity in the range
+    /// * `max` - The most critical severity in the range
+    pub fn new(min: SeverityType, max: SeverityType) -> Self {
+        Self { min, max }
+    }
+
+    /// Creates a severity range that contains every severity.
+    pub fn all() -> Self {
+        Self::new(SeverityType::min(), SeverityType::max())
+    }
+
+    /// Checks whether a severity is in the range.
+    ///
+    /// # Arguments
+    ///
+    /// * `severity` - The severity to check
+    pub fn contains(&self, severity: &SeverityType) -> bool {
+        severity.is_between(&self.min, &self.max)
+    }
 }
 
 /// A simple writer for console output.
@@ -40,7 +96,7 @@ enum ConsoleWriterDestination<'writer> {
 /// # };
 /// #
 /// // Add a console writer to the global logger
-/// Logger::<Severity, Message<Severity>>::global()
+/// Logger::<Severity, Message<Severity>>::global_mut()
 ///     .add_writer(
 ///         ConsoleWriter::new_stdout(
 ///             Plaintext::new_default()
@@ -56,7 +112,12 @@ pub struct ConsoleWriter<
     MessageType: HasSeverity<SeverityType> + HasText,
     FormatterType: Format<SeverityType, MessageType>,
 > {
-    destination: ConsoleWriterDestination<'writer>,
+    /// Where to write messages, each with the range of severities it is for. A destination without
+    /// a range accepts every severity.
+    destinations: Vec<(
+        Option<SeverityRange<SeverityType>>,
+        ConsoleWriterDestination<'writer>,
+    )>,
     formatter: FormatterType,
     severity_type_phantom: PhantomData<SeverityType>,
     message_type_phantom: PhantomData<MessageType>,
@@ -71,18 +132,62 @@ impl<
 {
     /// Create a new console writer that writes to stdout.
     pub fn new_stdout(formatter: FormatterType) -> Self {
-        Self {
-            destination: ConsoleWriterDestination::Stdout,
-            formatter,
-            severity_type_phantom: PhantomData,
-            message_type_phantom: PhantomData,
-        }
+        Self::new_unconditional(ConsoleWriterDestination::Stdout, formatter)
     }
 
     /// Create a new console writer that writes to stderr.
     pub fn new_stderr(formatter: FormatterType) -> Self {
+        Self::new_unconditional(ConsoleWriterDestination::Stderr, formatter)
+    }
+
+    /// Create a new console writer that chooses where to write each message based on its severity.
+    ///
+    /// Each message is written to the destination of the first range that contains its severity.
+    /// Messages whose severity is not in any of the ranges are not written.
+    ///
+    /// # Arguments
+    ///
+    /// * `destinations` - The severity ranges and where to write messages in them
+    /// * `formatter` - The formatter to use for messages
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::{
+    /// #     writers::{ConsoleWriter, ConsoleWriterDestination, SeverityRange},
+    /// #     formatters::Plaintext,
+    /// #     Logger,
+    /// #     Message,
+    /// #     Severity,
+    /// # };
+    /// #
+    /// // Send warnings and errors to stderr, and everything else to stdout
+    /// Logger::<Severity, Message<Severity>>::global_mut()
+    ///     .add_writer(
+    ///         ConsoleWriter::new_per_severity(
+    ///             vec![
+    ///                 (
+    ///                     SeverityRange::new(Severity::Warning, Severity::Fatal),
+    ///                     ConsoleWriterDestination::Stderr,
+    ///                 ),
+    ///                 (SeverityRange::all(), ConsoleWriterDestination::Stdout),
+    ///             ],
+    ///             Plaintext::new_default()
+    ///         )
+    ///     );
+    /// ```
+    pub fn new_per_severity(
+        destinations: Vec<(
+            SeverityRange<SeverityType>,
+            ConsoleWriterDestination<'writer>,
+        )>,
+        formatter: FormatterType,
+    ) -> Self {
         Self {
-            destination: ConsoleWriterDestination::Stderr,
+            destinations: destinations
+                .into_iter()
+                .map(|(range, destination)| (Some(range), destination))
+                .collect(),
             formatter,
             severity_type_phantom: PhantomData,
             message_type_phantom: PhantomData,
@@ -90,9 +195,44 @@ impl<
     }
 
     /// Create a new console writer that writes to a custom writer.
-    pub fn new_write(writer: &'writer mut dyn io::Write, formatter: FormatterType) -> Self {
+    ///
+    /// This is useful for hermetic tests that write to a buffer instead of the console. The writer
+    /// borrows the buffer, so drop it before reading the buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use got_ur_logs_uwu::{
+    /// #     writers::ConsoleWriter, formatters::Plaintext, FromCoreFields, Message, Severity, Write
+    /// # };
+    /// #
+    /// let mut buffer = Vec::new();
+    ///
+    /// let mut writer = ConsoleWriter::new_write(&mut buffer, Plaintext::new_default());
+    ///
+    /// writer
+    ///     .write(&Message::from_core_fields(Severity::Info, "hello, world"))
+    ///     .expect("writing to a buffer should succeed");
+    ///
+    /// drop(writer);
+    ///
+    /// assert_eq!(buffer, b"[info] hello, world");
+    /// ```
+    pub fn new_write(
+        writer: &'writer mut (dyn io::Write + Send),
+        formatter: FormatterType,
+    ) -> Self {
+        Self::new_unconditional(ConsoleWriterDestination::Writer(writer), formatter)
+    }
+
+    /// Create a new console writer that writes every message to a single destination, whatever
+    /// its severity.
+    fn new_unconditional(
+        destination: ConsoleWriterDestination<'writer>,
+        formatter: FormatterType,
+    ) -> Self {
         Self {
-            destination: ConsoleWriterDestination::Writer(writer),
+            destinations: vec![(None, destination)],
             formatter,
             severity_type_phantom: PhantomData,
             message_type_phantom: PhantomData,
@@ -109,24 +249,92 @@ impl<
     for ConsoleWriter<'writer, SeverityType, MessageType, FormatterType>
 {
     fn write(&mut self, message: &MessageType) -> Result<()> {
-        match self.destination {
-            ConsoleWriterDestination::Stdout => self.formatter.format(message, &mut io::stdout()),
-            ConsoleWriterDestination::Stderr => self.formatter.format(message, &mut io::stderr()),
-            ConsoleWriterDestination::Writer(ref mut writer) => {
-                self.formatter.format(message, writer)
+        let Some((_, destination)) = self.destinations.iter_mut().find(|(range, _)| {
+            range
+                .as_ref()
+                .is_none_or(|range| range.contains(message.severity()))
+        }) else {
+            return Ok(());
+        };
+
+        // Standard output and error are locked while formatting so that the formatter's writes
+        // are not interleaved with output from other threads
+        let result = panic::catch_unwind(AssertUnwindSafe(|| match destination {
+            ConsoleWriterDestination::Stdout => self
+                .formatter
+                .format_generic(message, &mut io::stdout().lock()),
+            ConsoleWriterDestination::Stderr => self
+                .formatter
+                .format_generic(message, &mut io::stderr().lock()),
+            ConsoleWriterDestination::Writer(writer) => self.formatter.format(message, *writer),
+        }));
+
+        result.unwrap_or_else(|payload| {
+            // Make a best effort to get the message out without the formatter. If even this fails
+            // there is nowhere left to report it.
+            let _ = writeln!(io::stderr(), "[{}] {}", message.severity(), message.text());
+
+            Err(Error::WriterError(format!(
+                "formatter panicked: {}",
+                panic_payload_message(payload.as_ref())
+            )))
+        })
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        // Buffered output is not for any one message, so it goes to the first destination
+        if let Some((_, destination)) = self.destinations.first_mut() {
+            match destination {
+                ConsoleWriterDestination::Stdout => {
+                    self.formatter.flush(&mut io::stdout().lock())?
+                }
+                ConsoleWriterDestination::Stderr => {
+                    self.formatter.flush(&mut io::stderr().lock())?
+                }
+                ConsoleWriterDestination::Writer(writer) => self.formatter.flush(*writer)?,
+            }
+        }
+
+        for (_, destination) in &mut self.destinations {
+            match destination {
+                ConsoleWriterDestination::Stdout => io::stdout().flush()?,
+                ConsoleWriterDestination::Stderr => io::stderr().flush()?,
+                ConsoleWriterDestination::Writer(writer) => writer.flush()?,
             }
         }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "console"
+    }
+}
+
+/// Gets a description of a panic from its payload.
+fn panic_payload_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown panic"
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{formatters::Plaintext, FromCoreFields, Message, Result, Severity};
+    use crate::{
+        formatters::Plaintext, test_utils::lock_console, FromCoreFields, Message, Result, Severity,
+    };
     use gag::BufferRedirect;
+    use std::io::Read;
 
     #[test]
     fn stdout() -> Result<()> {
+        let _console_lock = lock_console();
+
         let formatter = Plaintext::new_default();
 
         let mut writer = ConsoleWriter::new_stdout(formatter);
@@ -136,18 +344,148 @@ mod tests {
 
         writer.write(&Message::from_core_fields(Severity::Info, "hello, world"))?;
 
+        io::stdout().flush()?;
+
         let mut output_stdout = String::new();
         let mut output_stderr = String::new();
 
+        buffer_stdout.read_to_string(&mut output_stdout)?;
+        buffer_stderr.read_to_string(&mut output_stderr)?;
+
+        assert_eq!(output_stdout, "[info] hello, world");
+        assert_eq!(output_stderr, "");
+
+        Ok(())
+    }
+
+    #[test]
+    fn formatter_panic() {
+        struct PanickingFormatter;
+
+        impl Format<Severity, Message<Severity>> for PanickingFormatter {
+            fn format(
+                &mut self,
+                _message: &Message<Severity>,
+                _writer: &mut dyn io::Write,
+            ) -> Result<()> {
+                panic!("formatter is broken");
+            }
+        }
+
+        let _console_lock = lock_console();
+
+        let mut buffer = Vec::new();
+
+        let mut writer = ConsoleWriter::new_write(&mut buffer, PanickingFormatter);
+
+        let error = writer
+            .write(&Message::from_core_fields(Severity::Info, "hello, world"))
+            .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Log writer failed: formatter panicked: formatter is broken"
+        );
+    }
+
+    #[test]
+    fn write_to_vec() -> Result<()> {
+        let mut buffer = Vec::new();
+
+        {
+            let mut writer = ConsoleWriter::new_write(&mut buffer, Plaintext::new_default());
+
+            writer.write(&Message::from_core_fields(Severity::Info, "first"))?;
+            writer.write(&Message::from_core_fields(Severity::Error, "second"))?;
+            writer.flush()?;
+        }
+
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "[info] first[error] second"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn write_every_severity() -> Result<()> {
+        let mut buffer = Vec::new();
+
+        {
+            let mut writer =
+                ConsoleWriter::new_write(&mut buffer, Plaintext::new("{{text}};").unwrap());
+
+            // Custom severities at the extreme levels are written too
+            writer.write(&Message::from_core_fields(
+                Severity::Custom(0, "lowest"),
+                "lowest",
+            ))?;
+            writer.write(&Message::from_core_fields(
+                Severity::Custom(255, "highest"),
+                "highest",
+            ))?;
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), "lowest;highest;");
+
+        Ok(())
+    }
+
+    #[test]
+    fn per_severity() -> Result<()> {
+        let mut buffer_info = Vec::new();
+        let mut buffer_errors = Vec::new();
+
+        {
+            let mut writer = ConsoleWriter::new_per_severity(
+                vec![
+                    (
+                        SeverityRange::new(Severity::Warning, Severity::Fatal),
+                        ConsoleWriterDestination::Writer(&mut buffer_errors),
+                    ),
+                    (
+                        SeverityRange::new(Severity::Info, Severity::Fatal),
+                        ConsoleWriterDestination::Writer(&mut buffer_info),
+                    ),
+                ],
+                Plaintext::new_default(),
+            );
+
+            writer.write(&Message::from_core_fields(Severity::Debug, "dropped"))?;
+            writer.write(&Message::from_core_fields(Severity::Info, "first"))?;
+            writer.write(&Message::from_core_fields(Severity::Error, "second"))?;
+            writer.write(&Message::from_core_fields(Severity::Warning, "third"))?;
+            writer.flush()?;
+        }
+
+        assert_eq!(String::from_utf8(buffer_info).unwrap(), "[info] first");
+        assert_eq!(
+            String::from_utf8(buffer_errors).unwrap(),
+            "[error] second[warning] third"
+        );
+
         Ok(())
     }
 
     #[test]
     fn stderr() -> Result<()> {
+        let _console_lock = lock_console();
+
         let formatter = Plaintext::new_default();
 
         let mut writer = ConsoleWriter::new_stdout(formatter);
 
         writer.write(&Message::from_core_fields(Severity::Info, "hello, world"))
     }
+
+    #[test]
+    fn name() {
+        assert_eq!(
+            Write::<Severity, Message<Severity>>::name(&ConsoleWriter::new_stdout(
+                Plaintext::new_default()
+            )),
+            "console"
+        );
+    }
 }
@@ -0,0 +1,238 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    traits::{Format, HasSeverity, HasText},
+    writers::end_line,
+    Clock, IsSeverity, Result, SystemClock, Write,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, Write as _},
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+
+/// A writer that appends messages to a new file each day.
+///
+/// Files are named like `<prefix>-YYYY-MM-DD.log` and placed in the given directory. Every write
+/// checks the current UTC date and opens a new file once the date has changed.
+///
+/// # Example
+///
+/// ```no_run
+/// # use got_ur_logs_uwu::{
+/// #     writers::DailyRollingFileWriter,
+/// #     formatters::Plaintext,
+/// #     Logger,
+/// #     Message,
+/// #     Severity,
+/// # };
+/// #
+/// // Writes to files like logs/app-2023-09-30.log
+/// Logger::<Severity, Message<Severity>>::global_mut()
+///     .add_writer(
+///         DailyRollingFileWriter::new(
+///             "logs",
+///             "app",
+///             Plaintext::new_default()
+///         )
+///     );
+/// ```
+pub struct DailyRollingFileWriter<
+    SeverityType: IsSeverity,
+    MessageType: HasSeverity<SeverityType> + HasText,
+    FormatterType: Format<SeverityType, MessageType>,
+> {
+    directory: PathBuf,
+    prefix: String,
+    formatter: FormatterType,
+    clock: Box<dyn Clock>,
+    current: Option<(NaiveDate, File)>,
+    severity_type_phantom: PhantomData<SeverityType>,
+    message_type_phantom: PhantomData<MessageType>,
+}
+
+impl<
+        SeverityType: IsSeverity,
+        MessageType: HasSeverity<SeverityType> + HasText,
+        FormatterType: Format<SeverityType, MessageType>,
+    > DailyRollingFileWriter<SeverityType, MessageType, FormatterType>
+{
+    /// Create a new daily rolling file writer.
+    ///
+    /// No files are opened until the first message is written.
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - The directory to create the log files in, which must already exist
+    /// * `prefix` - The prefix of the log file names
+    /// * `formatter` - The formatter to use for messages
+    pub fn new<PathType: AsRef<Path>, StringType: Into<String>>(
+        directory: PathType,
+        prefix: StringType,
+        formatter: FormatterType,
+    ) -> Self {
+        Self {
+            directory: directory.as_ref().to_owned(),
+            prefix: prefix.into(),
+            formatter,
+            clock: Box::new(SystemClock),
+            current: None,
+            severity_type_phantom: PhantomData,
+            message_type_phantom: PhantomData,
+        }
+    }
+
+    /// Replaces the clock that is used to determine the current date.
+    ///
+    /// By default this is [`SystemClock`].
+    pub fn with_clock<ClockType: 'static + Clock>(mut self, clock: ClockType) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    /// Gets the path of the log file for a given date.
+    pub fn path_for_date(&self, date: NaiveDate) -> PathBuf {
+        self.directory
+            .join(format!("{}-{}.log", self.prefix, date.format("%Y-%m-%d")))
+    }
+
+    /// Gets the file for the current date, opening a new one if the date has changed.
+    fn current_file(&mut self) -> io::Result<&mut File> {
+        let today = DateTime::<Utc>::from(self.clock.now()).date_naive();
+
+        if !matches!(self.current, Some((date, _)) if date == today) {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(self.path_for_date(today))?;
+
+            self.current = Some((today, file));
+        }
+
+        Ok(&mut self
+            .current
+            .as_mut()
+            .expect("current file should have been opened above")
+            .1)
+    }
+}
+
+impl<
+        SeverityType: IsSeverity,
+        MessageType: HasSeverity<SeverityType> + HasText,
+        FormatterType: Format<SeverityType, MessageType>,
+    > Write<SeverityType, MessageType>
+    for DailyRollingFileWriter<SeverityType, MessageType, FormatterType>
+{
+    fn write(&mut self, message: &MessageType) -> Result<()> {
+        // Format into a buffer first so that the file and formatter are not borrowed at once
+        let mut buffer = Vec::new();
+
+        self.formatter.format_generic(message, &mut buffer)?;
+        end_line(&mut buffer);
+
+        let file = self.current_file()?;
+
+        file.write_all(&buffer)?;
+        file.flush()?;
+
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        let mut buffer = Vec::new();
+
+        self.formatter.flush(&mut buffer)?;
+        end_line(&mut buffer);
+
+        if !buffer.is_empty() {
+            self.current_file()?.write_all(&buffer)?;
+        }
+
+        if let Some((_, file)) = &mut self.current {
+            file.flush()?;
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "daily_rolling_file"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{formatters::Plaintext, test_utils::FakeClock, FromCoreFields, Message, Severity};
+    use std::{
+        env, fs, process,
+        time::{Duration, SystemTime},
+    };
+
+    #[test]
+    fn rolls_over_each_day() -> Result<()> {
+        let directory = env::temp_dir().join(format!(
+            "got-ur-logs-uwu-daily-rolling-file-{}",
+            process::id()
+        ));
+
+        fs::create_dir_all(&directory)?;
+
+        // 2023-09-30T12:00:00Z
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_696_075_200);
+
+        let clock = FakeClock::at(start);
+
+        let mut writer = DailyRollingFileWriter::new(&directory, "app", Plaintext::new_default())
+            .with_clock(clock.clone());
+
+        writer.write(&Message::from_core_fields(Severity::Info, "first"))?;
+        writer.write(&Message::from_core_fields(Severity::Info, "second"))?;
+
+        clock.advance(Duration::from_secs(24 * 60 * 60));
+
+        writer.write(&Message::from_core_fields(Severity::Info, "third"))?;
+
+        assert_eq!(
+            fs::read_to_string(directory.join("app-2023-09-30.log"))?,
+            "[info] first\n[info] second\n"
+        );
+        assert_eq!(
+            fs::read_to_string(directory.join("app-2023-10-01.log"))?,
+            "[info] third\n"
+        );
+
+        fs::remove_dir_all(&directory)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn missing_directory() {
+        let mut writer = DailyRollingFileWriter::new(
+            env::temp_dir().join("got-ur-logs-uwu-missing-directory/nested"),
+            "app",
+            Plaintext::new_default(),
+        );
+
+        assert!(writer
+            .write(&Message::from_core_fields(Severity::Info, "first"))
+            .is_err());
+    }
+}
@@ -0,0 +1,368 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    traits::{Format, HasSeverity, HasText},
+    writers::end_line,
+    Error, IsSeverity, Result, Write,
+};
+#[cfg(feature = "compression")]
+use flate2::{write::GzEncoder, Compression};
+#[cfg(feature = "compression")]
+use std::{fs, io::BufWriter};
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, Write as _},
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+
+/// A writer that appends messages to a file.
+///
+/// Each message is followed by a newline, and the file is flushed after every message so that
+/// logs are not lost if the program crashes. Files can also be compressed with gzip when the
+/// `compression` feature is enabled, see [`FileWriter::compressed`].
+///
+/// # Example
+///
+/// ```no_run
+/// # use got_ur_logs_uwu::{
+/// #     writers::FileWriter,
+/// #     formatters::Plaintext,
+/// #     Logger,
+/// #     Message,
+/// #     Severity,
+/// # };
+/// #
+/// Logger::<Severity, Message<Severity>>::global_mut()
+///     .add_writer(
+///         FileWriter::open(
+///             "app.log",
+///             Plaintext::new_default()
+///         )
+///         .expect("unable to open log file")
+///     );
+/// ```
+pub struct FileWriter<
+    SeverityType: IsSeverity,
+    MessageType: HasSeverity<SeverityType> + HasText,
+    FormatterType: Format<SeverityType, MessageType>,
+> {
+    path: PathBuf,
+    output: FileOutput,
+    formatter: FormatterType,
+    severity_type_phantom: PhantomData<SeverityType>,
+    message_type_phantom: PhantomData<MessageType>,
+}
+
+impl<
+        SeverityType: IsSeverity,
+        MessageType: HasSeverity<SeverityType> + HasText,
+        FormatterType: Format<SeverityType, MessageType>,
+    > FileWriter<SeverityType, MessageType, FormatterType>
+{
+    /// Opens a file writer.
+    ///
+    /// The file is created if it does not exist, and appended to if it does.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the log file, whose directory must already exist
+    /// * `formatter` - The formatter to use for messages
+    ///
+    /// # Returns
+    ///
+    /// Returns the writer, or an error if the file could not be opened for writing.
+    pub fn open<PathType: AsRef<Path>>(path: PathType, formatter: FormatterType) -> Result<Self> {
+        let path = path.as_ref().to_owned();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+
+        Ok(Self {
+            path,
+            output: FileOutput::Plain(file),
+            formatter,
+            severity_type_phantom: PhantomData,
+            message_type_phantom: PhantomData,
+        })
+    }
+
+    /// Compresses the log file with gzip.
+    ///
+    /// The file is renamed to have a `.log.gz` extension, so `app.log` becomes `app.log.gz`. If
+    /// the uncompressed file that was opened is empty it is removed. Compressed output is
+    /// buffered and only written to the file when the writer is flushed, since flushing the
+    /// compressor after every message would hurt the compression ratio.
+    ///
+    /// The compressed stream must be finished for the file to be valid gzip. This happens when the
+    /// writer is dropped, or explicitly with [`FileWriter::shutdown`].
+    ///
+    /// Requires the `compression` feature.
+    ///
+    /// # Returns
+    ///
+    /// Returns the writer, or an error if the compressed file could not be opened for writing.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use got_ur_logs_uwu::{formatters::Plaintext, writers::FileWriter, Message, Severity};
+    /// #
+    /// let writer = FileWriter::<Severity, Message<Severity>, _>::open(
+    ///     "app.log",
+    ///     Plaintext::new_default(),
+    /// )
+    /// .and_then(FileWriter::compressed)
+    /// .expect("unable to open log file");
+    ///
+    /// assert_eq!(writer.path().to_str(), Some("app.log.gz"));
+    /// ```
+    #[cfg(feature = "compression")]
+    pub fn compressed(mut self) -> Result<Self> {
+        let path = self.path.with_extension("log.gz");
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+
+        if path != self.path && fs::metadata(&self.path)?.len() == 0 {
+            fs::remove_file(&self.path)?;
+        }
+
+        self.path = path;
+        self.output =
+            FileOutput::Compressed(GzEncoder::new(BufWriter::new(file), Compression::default()));
+
+        Ok(self)
+    }
+
+    /// Shuts down the writer, writing out anything that is buffered and finishing the compressed
+    /// stream if the file is compressed.
+    ///
+    /// Any messages written after the writer has been shut down are rejected with an error.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if everything was written out, or an error if it was not.
+    pub fn shutdown(&mut self) -> Result<()> {
+        Write::flush(self)?;
+
+        match std::mem::replace(&mut self.output, FileOutput::Shutdown) {
+            #[cfg(feature = "compression")]
+            FileOutput::Compressed(encoder) => {
+                encoder.finish()?.flush()?;
+            }
+            FileOutput::Plain(_) | FileOutput::Shutdown => {}
+        }
+
+        Ok(())
+    }
+
+    /// Gets the path of the log file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Gets mutable access to the formatter, for writers that wrap this one.
+    pub(crate) fn formatter_mut(&mut self) -> &mut FormatterType {
+        &mut self.formatter
+    }
+}
+
+/// Where a file writer writes formatted messages.
+enum FileOutput {
+    /// The file is written to directly.
+    Plain(File),
+    /// The file is compressed with gzip.
+    #[cfg(feature = "compression")]
+    Compressed(GzEncoder<BufWriter<File>>),
+    /// The writer has been shut down.
+    Shutdown,
+}
+
+impl FileOutput {
+    /// Writes a formatted message.
+    ///
+    /// Plain files are flushed right away so that messages are not lost if the program crashes.
+    fn write_all(&mut self, buffer: &[u8]) -> Result<()> {
+        match self {
+            Self::Plain(file) => {
+                file.write_all(buffer)?;
+                file.flush()?;
+            }
+            #[cfg(feature = "compression")]
+            Self::Compressed(encoder) => encoder.write_all(buffer)?,
+            Self::Shutdown => {
+                return Err(Error::WriterError(
+                    "file writer has been shut down".to_owned(),
+                ))
+            }
+        }
+
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Plain(file) => file.flush(),
+            #[cfg(feature = "compression")]
+            Self::Compressed(encoder) => encoder.flush(),
+            Self::Shutdown => Ok(()),
+        }
+    }
+}
+
+impl<
+        SeverityType: IsSeverity,
+        MessageType: HasSeverity<SeverityType> + HasText,
+        FormatterType: Format<SeverityType, MessageType>,
+    > Write<SeverityType, MessageType> for FileWriter<SeverityType, MessageType, FormatterType>
+{
+    fn write(&mut self, message: &MessageType) -> Result<()> {
+        // Format into a buffer first so that each message is written with a single call
+        let mut buffer = Vec::new();
+
+        self.formatter.format_generic(message, &mut buffer)?;
+        end_line(&mut buffer);
+
+        self.output.write_all(&buffer)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        let mut buffer = Vec::new();
+
+        self.formatter.flush(&mut buffer)?;
+        end_line(&mut buffer);
+
+        if !buffer.is_empty() {
+            self.output.write_all(&buffer)?;
+        }
+
+        self.output.flush()?;
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "file"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        formatters::{Json, Plaintext},
+        FromCoreFields, Message, Severity,
+    };
+    #[cfg(feature = "compression")]
+    use flate2::read::GzDecoder;
+    #[cfg(feature = "compression")]
+    use std::io::Read;
+    use std::{env, fs, process};
+
+    #[test]
+    fn writes_messages() -> Result<()> {
+        let path = env::temp_dir().join(format!("got-ur-logs-uwu-file-{}.log", process::id()));
+
+        fs::write(&path, "existing\n")?;
+
+        let mut writer = FileWriter::open(&path, Plaintext::new_default())?;
+
+        writer.write(&Message::from_core_fields(Severity::Info, "first"))?;
+        writer.write(&Message::from_core_fields(Severity::Warning, "second"))?;
+        writer.write(&Message::from_core_fields(Severity::Error, "third"))?;
+
+        assert_eq!(
+            fs::read_to_string(writer.path())?,
+            "existing\n[info] first\n[warning] second\n[error] third\n"
+        );
+
+        fs::remove_file(&path)?;
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "compression")]
+    fn compressed() -> Result<()> {
+        let path = env::temp_dir().join(format!(
+            "got-ur-logs-uwu-file-compressed-{}.log",
+            process::id()
+        ));
+
+        let mut writer = FileWriter::open(&path, Plaintext::new_default())?.compressed()?;
+
+        assert_eq!(writer.path(), path.with_extension("log.gz"));
+        assert!(!path.exists());
+
+        writer.write(&Message::from_core_fields(Severity::Info, "first"))?;
+        writer.write(&Message::from_core_fields(Severity::Error, "second"))?;
+        writer.shutdown()?;
+
+        let mut contents = String::new();
+
+        GzDecoder::new(File::open(writer.path())?).read_to_string(&mut contents)?;
+
+        assert_eq!(contents, "[info] first\n[error] second\n");
+        assert!(writer
+            .write(&Message::from_core_fields(Severity::Info, "third"))
+            .is_err());
+
+        fs::remove_file(writer.path())?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn flushes_batched_output() -> Result<()> {
+        let path =
+            env::temp_dir().join(format!("got-ur-logs-uwu-file-batch-{}.log", process::id()));
+
+        let mut writer = FileWriter::open(&path, Json::new().batch_mode(true))?;
+
+        writer.write(&Message::from_core_fields(Severity::Info, "first"))?;
+        writer.write(&Message::from_core_fields(Severity::Info, "second"))?;
+
+        assert_eq!(fs::read_to_string(writer.path())?, "");
+
+        writer.flush()?;
+
+        let contents = fs::read_to_string(writer.path())?;
+
+        assert!(contents.starts_with('['));
+        assert!(contents.ends_with("]\n"));
+        assert_eq!(contents.lines().count(), 1);
+
+        fs::remove_file(&path)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn missing_directory() {
+        assert!(FileWriter::<Severity, Message<Severity>, _>::open(
+            env::temp_dir().join("got-ur-logs-uwu-missing-directory/nested/app.log"),
+            Plaintext::new_default(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn not_writable() {
+        // A directory cannot be opened as a file
+        assert!(FileWriter::<Severity, Message<Severity>, _>::open(
+            env::temp_dir(),
+            Plaintext::new_default()
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,368 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+#[cfg(feature = "compression")]
+use std::io::Write as _;
+use std::{
+    io,
+    marker::PhantomData,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs, UdpSocket},
+    process,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+#[cfg(feature = "compression")]
+use flate2::{
+    write::{GzEncoder, ZlibEncoder},
+    Compression,
+};
+
+use crate::{
+    formatters::Gelf,
+    traits::{Format, HasFields, HasSeverity, HasText, HasTimestamp},
+    IsSeverity, Result, Write,
+};
+
+/// The default maximum size of each UDP datagram, as recommended by the GELF specification.
+const DEFAULT_MAX_DATAGRAM_SIZE: usize = 8192;
+
+/// How GELF payloads are compressed before they are sent.
+///
+/// Compressed payloads require the `compression` feature.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CompressionType {
+    /// Payloads are sent uncompressed.
+    #[default]
+    None,
+    /// Payloads are compressed with GZIP.
+    #[cfg(feature = "compression")]
+    Gzip,
+    /// Payloads are compressed with ZLIB.
+    #[cfg(feature = "compression")]
+    Zlib,
+}
+
+/// A writer that sends messages to a Graylog server as GELF UDP datagrams.
+///
+/// Messages are formatted with [`Gelf`], optionally compressed, and split into chunks if they do
+/// not fit in a single datagram.
+///
+/// # Example
+///
+/// ```no_run
+/// # use got_ur_logs_uwu::{writers::GelfUdpWriter, Logger, Message, Severity};
+/// #
+/// Logger::<Severity, Message<Severity>>::global_mut()
+///     .add_writer(
+///         GelfUdpWriter::new("graylog.example.com:12201")
+///             .expect("unable to create socket")
+///     );
+/// ```
+pub struct GelfUdpWriter<
+    SeverityType: IsSeverity,
+    MessageType: HasSeverity<SeverityType> + HasText + HasFields + HasTimestamp,
+> {
+    socket: UdpSocket,
+    formatter: Gelf,
+    compression: CompressionType,
+    max_datagram_size: usize,
+    next_message_id: u64,
+    severity_type_phantom: PhantomData<SeverityType>,
+    message_type_phantom: PhantomData<MessageType>,
+}
+
+impl<
+        SeverityType: IsSeverity,
+        MessageType: HasSeverity<SeverityType> + HasText + HasFields + HasTimestamp,
+    > GelfUdpWriter<SeverityType, MessageType>
+{
+    /// Create a new GELF UDP writer.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The address of the Graylog GELF UDP input
+    ///
+    /// # Returns
+    ///
+    /// Returns an error if the socket could not be created or the address could not be resolved.
+    pub fn new<AddressType: ToSocketAddrs>(address: AddressType) -> Result<Self> {
+        let socket = Self::connect(address)?;
+
+        // Seed message IDs so that they are unlikely to collide with other processes
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos() as u64
+            ^ (u64::from(process::id()) << 32);
+
+        Ok(Self {
+            socket,
+            formatter: Gelf::new(),
+            compression: CompressionType::None,
+            max_datagram_size: DEFAULT_MAX_DATAGRAM_SIZE,
+            next_message_id: seed,
+            severity_type_phantom: PhantomData,
+            message_type_phantom: PhantomData,
+        })
+    }
+
+    /// Sets how payloads are compressed before they are sent.
+    ///
+    /// By default they are not compressed.
+    ///
+    /// # Arguments
+    ///
+    /// * `compression` - The compression to use
+    pub fn with_compression(mut self, compression: CompressionType) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Sets the maximum size of each datagram, including the chunk header.
+    ///
+    /// By default this is 8192 bytes. Lower values, like 1420, are safer across networks with a
+    /// smaller MTU.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_datagram_size` - The maximum size in bytes
+    pub fn with_max_datagram_size(mut self, max_datagram_size: usize) -> Self {
+        self.max_datagram_size = max_datagram_size;
+        self
+    }
+
+    /// Replaces the formatter.
+    ///
+    /// # Arguments
+    ///
+    /// * `formatter` - The formatter to use
+    pub fn with_formatter(mut self, formatter: Gelf) -> Self {
+        self.formatter = formatter;
+        self
+    }
+
+    /// Creates a socket connected to the first resolved address that can be reached.
+    ///
+    /// The socket is bound to the unspecified address of the same family as the address it
+    /// connects to, since an IPv4 socket cannot send to an IPv6 address or the other way around.
+    fn connect<AddressType: ToSocketAddrs>(address: AddressType) -> io::Result<UdpSocket> {
+        let mut last_error = None;
+
+        for address in address.to_socket_addrs()? {
+            let local_address = match address {
+                SocketAddr::V4(_) => SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0)),
+                SocketAddr::V6(_) => SocketAddr::from((Ipv6Addr::UNSPECIFIED, 0)),
+            };
+
+            match UdpSocket::bind(local_address).and_then(|socket| {
+                socket.connect(address)?;
+                Ok(socket)
+            }) {
+                Ok(socket) => return Ok(socket),
+                Err(error) => last_error = Some(error),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "address did not resolve to anything",
+            )
+        }))
+    }
+
+    /// Compresses a payload according to the writer's compression type.
+    fn compress(&self, payload: Vec<u8>) -> io::Result<Vec<u8>> {
+        match self.compression {
+            CompressionType::None => Ok(payload),
+            #[cfg(feature = "compression")]
+            CompressionType::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(&payload)?;
+                encoder.finish()
+            }
+            #[cfg(feature = "compression")]
+            CompressionType::Zlib => {
+                let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(&payload)?;
+                encoder.finish()
+            }
+        }
+    }
+}
+
+impl<
+        SeverityType: 'static + IsSeverity,
+        MessageType: HasSeverity<SeverityType> + HasText + HasFields + HasTimestamp,
+    > Write<SeverityType, MessageType> for GelfUdpWriter<SeverityType, MessageType>
+{
+    fn write(&mut self, message: &MessageType) -> Result<()> {
+        let mut payload = Vec::new();
+
+        self.formatter.format_generic(message, &mut payload)?;
+
+        let payload = self.compress(payload)?;
+
+        let message_id = self.next_message_id;
+
+        self.next_message_id = message_id.wrapping_add(1);
+
+        for datagram in Gelf::chunk(&payload, message_id, self.max_datagram_size)? {
+            self.socket.send(&datagram)?;
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "gelf_udp"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "compression")]
+    use std::io::Read;
+    use std::time::Duration;
+
+    #[cfg(feature = "compression")]
+    use flate2::read::{GzDecoder, ZlibDecoder};
+    use serde_json::Value;
+
+    use super::*;
+    use crate::{FromCoreFields, Message, Severity};
+
+    fn receiver() -> UdpSocket {
+        receiver_at("127.0.0.1:0")
+    }
+
+    fn receiver_at(address: &str) -> UdpSocket {
+        let socket = UdpSocket::bind(address).unwrap();
+
+        socket
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+
+        socket
+    }
+
+    fn receive(socket: &UdpSocket) -> Vec<u8> {
+        let mut buffer = [0; 65536];
+
+        let size = socket.recv(&mut buffer).unwrap();
+
+        buffer[..size].to_vec()
+    }
+
+    fn send(compression: CompressionType, max_datagram_size: usize, text: &str) -> UdpSocket {
+        let socket = receiver();
+
+        let mut writer = GelfUdpWriter::new(socket.local_addr().unwrap())
+            .unwrap()
+            .with_compression(compression)
+            .with_max_datagram_size(max_datagram_size);
+
+        writer
+            .write(&Message::from_core_fields(Severity::Info, text))
+            .unwrap();
+
+        socket
+    }
+
+    #[test]
+    fn uncompressed() {
+        let socket = send(CompressionType::None, DEFAULT_MAX_DATAGRAM_SIZE, "hello");
+
+        let object: Value = serde_json::from_slice(&receive(&socket)).unwrap();
+
+        assert_eq!(object["short_message"], "hello");
+    }
+
+    #[test]
+    fn ipv6() {
+        let socket = receiver_at("[::1]:0");
+
+        let mut writer = GelfUdpWriter::new(socket.local_addr().unwrap()).unwrap();
+
+        writer
+            .write(&Message::from_core_fields(Severity::Info, "hello"))
+            .unwrap();
+
+        let object: Value = serde_json::from_slice(&receive(&socket)).unwrap();
+
+        assert_eq!(object["short_message"], "hello");
+    }
+
+    #[test]
+    #[cfg(feature = "compression")]
+    fn gzip() {
+        let socket = send(CompressionType::Gzip, DEFAULT_MAX_DATAGRAM_SIZE, "hello");
+
+        let mut payload = String::new();
+
+        GzDecoder::new(receive(&socket).as_slice())
+            .read_to_string(&mut payload)
+            .unwrap();
+
+        let object: Value = serde_json::from_str(&payload).unwrap();
+
+        assert_eq!(object["short_message"], "hello");
+    }
+
+    #[test]
+    #[cfg(feature = "compression")]
+    fn zlib() {
+        let socket = send(CompressionType::Zlib, DEFAULT_MAX_DATAGRAM_SIZE, "hello");
+
+        let mut payload = String::new();
+
+        ZlibDecoder::new(receive(&socket).as_slice())
+            .read_to_string(&mut payload)
+            .unwrap();
+
+        let object: Value = serde_json::from_str(&payload).unwrap();
+
+        assert_eq!(object["short_message"], "hello");
+    }
+
+    #[test]
+    fn chunked() {
+        let text = "x".repeat(1000);
+
+        let socket = send(CompressionType::None, 256, &text);
+
+        let first = receive(&socket);
+
+        let chunk_count = first[11] as usize;
+
+        let mut chunks = vec![first];
+
+        for _ in 1..chunk_count {
+            chunks.push(receive(&socket));
+        }
+
+        // UDP on the loopback interface does not reorder datagrams, but sort to be safe
+        chunks.sort_by_key(|chunk| chunk[10]);
+
+        let payload = chunks
+            .iter()
+            .flat_map(|chunk| chunk[12..].iter().copied())
+            .collect::<Vec<_>>();
+
+        let object: Value = serde_json::from_slice(&payload).unwrap();
+
+        assert_eq!(object["short_message"], text.as_str());
+    }
+}
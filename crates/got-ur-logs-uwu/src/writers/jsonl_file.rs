@@ -0,0 +1,192 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+use std::{mem, path::Path};
+
+use crate::{
+    formatters::{Json, JsonFieldNames},
+    writers::FileWriter,
+    HasCorrelationId, HasFields, HasId, HasSeverity, HasSourceLocation, HasTags, HasText,
+    HasTimestamp, IsSeverity, Result, Write,
+};
+
+/// A writer that appends messages to a file as newline-delimited JSON.
+///
+/// This is a [`FileWriter`] with a [`Json`] formatter, for the common case of writing logs that are
+/// picked up by a log aggregator.
+///
+/// # Example
+///
+/// ```no_run
+/// # use got_ur_logs_uwu::{writers::JsonlFileWriter, Logger, Message, Severity};
+/// #
+/// Logger::<Severity, Message<Severity>>::global_mut()
+///     .add_writer(JsonlFileWriter::new("app.jsonl").expect("unable to open log file"));
+/// ```
+pub struct JsonlFileWriter<
+    SeverityType: IsSeverity,
+    MessageType: HasSeverity<SeverityType>
+        + HasText
+        + HasFields
+        + HasId
+        + HasTimestamp
+        + HasSourceLocation
+        + HasTags
+        + HasCorrelationId,
+> {
+    inner: FileWriter<SeverityType, MessageType, Json>,
+}
+
+impl<
+        SeverityType: IsSeverity,
+        MessageType: HasSeverity<SeverityType>
+            + HasText
+            + HasFields
+            + HasId
+            + HasTimestamp
+            + HasSourceLocation
+            + HasTags
+            + HasCorrelationId,
+    > JsonlFileWriter<SeverityType, MessageType>
+{
+    /// Opens a JSON lines file writer.
+    ///
+    /// The file is created if it does not exist, and appended to if it does.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the log file, whose directory must already exist
+    ///
+    /// # Returns
+    ///
+    /// Returns the writer, or an error if the file could not be opened for writing.
+    pub fn new<PathType: AsRef<Path>>(path: PathType) -> Result<Self> {
+        Ok(Self {
+            inner: FileWriter::open(path, Json::new())?,
+        })
+    }
+
+    /// Renames the keys that the core fields of each message are written under.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_names` - The keys to use
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use got_ur_logs_uwu::{
+    /// #     formatters::JsonFieldNames, writers::JsonlFileWriter, Message, Severity
+    /// # };
+    /// #
+    /// let writer = JsonlFileWriter::<Severity, Message<Severity>>::new("app.jsonl")
+    ///     .expect("unable to open log file")
+    ///     .with_field_names(JsonFieldNames {
+    ///         text: "message".to_owned(),
+    ///         ..JsonFieldNames::default()
+    ///     });
+    /// ```
+    pub fn with_field_names(mut self, field_names: JsonFieldNames) -> Self {
+        let formatter = self.inner.formatter_mut();
+
+        *formatter = mem::take(formatter).with_field_names(field_names);
+
+        self
+    }
+
+    /// Gets the path of the log file.
+    pub fn path(&self) -> &Path {
+        self.inner.path()
+    }
+}
+
+impl<
+        SeverityType: IsSeverity,
+        MessageType: HasSeverity<SeverityType>
+            + HasText
+            + HasFields
+            + HasId
+            + HasTimestamp
+            + HasSourceLocation
+            + HasTags
+            + HasCorrelationId,
+    > Write<SeverityType, MessageType> for JsonlFileWriter<SeverityType, MessageType>
+{
+    fn write(&mut self, message: &MessageType) -> Result<()> {
+        self.inner.write(message)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+
+    fn name(&self) -> &'static str {
+        "jsonl_file"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use serde_json::Value;
+
+    use super::*;
+    use crate::{FromCoreFields, Message, Severity};
+
+    #[test]
+    fn writes_json_lines() -> Result<()> {
+        let path = env::temp_dir().join(format!("got-ur-logs-uwu-jsonl-{}.jsonl", process::id()));
+
+        let mut writer = JsonlFileWriter::new(&path)?.with_field_names(JsonFieldNames {
+            text: "message".to_owned(),
+            ..JsonFieldNames::default()
+        });
+
+        writer.write(&Message::from_core_fields(Severity::Info, "first"))?;
+        writer.write(&Message::from_core_fields(Severity::Error, "second"))?;
+
+        let lines = fs::read_to_string(writer.path())?
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).expect("line should be valid JSON"))
+            .collect::<Vec<_>>();
+
+        fs::remove_file(&path)?;
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["severity"], "info");
+        assert_eq!(lines[0]["message"], "first");
+        assert_eq!(lines[1]["severity"], "error");
+        assert_eq!(lines[1]["message"], "second");
+
+        Ok(())
+    }
+
+    #[test]
+    fn name() -> Result<()> {
+        let path = env::temp_dir().join(format!(
+            "got-ur-logs-uwu-jsonl-name-{}.jsonl",
+            process::id()
+        ));
+
+        let writer = JsonlFileWriter::<Severity, Message<Severity>>::new(&path)?;
+
+        assert_eq!(writer.name(), "jsonl_file");
+
+        fs::remove_file(&path)?;
+
+        Ok(())
+    }
+}
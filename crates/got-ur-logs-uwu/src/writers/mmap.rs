@@ -0,0 +1,399 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    traits::{Format, HasSeverity, HasText},
+    writers::end_line,
+    Error, IsSeverity, Result, Write,
+};
+use memmap2::MmapMut;
+use std::{
+    fs::{self, File, OpenOptions},
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+
+/// What an [`MmapWriter`] does when its file is too full for the next message.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MmapRolloverPolicy {
+    /// Renames the full file by appending `.1` to its name, replacing any earlier rolled over
+    /// file, and starts writing to a new file at the original path.
+    #[default]
+    Rotate,
+
+    /// Starts writing again from the beginning of the same file, overwriting the oldest messages.
+    Wrap,
+
+    /// Refuses to write the message and returns an error.
+    Error,
+}
+
+/// A writer that writes messages into a memory-mapped file.
+///
+/// Requires the `mmap` feature. The file is pre-allocated to a fixed size when the writer is
+/// created and messages are copied into the mapping one after another, each followed by a newline,
+/// which avoids a system call per message. Space that has not been written to yet is filled with
+/// zero bytes, as are the leftover parts of messages that have been partly overwritten. When a
+/// message does not fit in the space that is left, the writer rolls over according to its
+/// [`MmapRolloverPolicy`].
+///
+/// Changes are written back to the file by the operating system in the background, or immediately
+/// when the writer is flushed.
+///
+/// # Example
+///
+/// ```no_run
+/// # use got_ur_logs_uwu::{
+/// #     writers::MmapWriter,
+/// #     formatters::Plaintext,
+/// #     Logger,
+/// #     Message,
+/// #     Severity,
+/// # };
+/// #
+/// // Writes to a 16 MiB file
+/// Logger::<Severity, Message<Severity>>::global_mut()
+///     .add_writer(
+///         MmapWriter::new(
+///             "app.log",
+///             16 * 1024 * 1024,
+///             Plaintext::new_default()
+///         )
+///         .expect("unable to map log file")
+///     );
+/// ```
+pub struct MmapWriter<
+    SeverityType: IsSeverity,
+    MessageType: HasSeverity<SeverityType> + HasText,
+    FormatterType: Format<SeverityType, MessageType>,
+> {
+    path: PathBuf,
+    capacity: usize,
+    formatter: FormatterType,
+    rollover_policy: MmapRolloverPolicy,
+    map: MmapMut,
+    offset: usize,
+    severity_type_phantom: PhantomData<SeverityType>,
+    message_type_phantom: PhantomData<MessageType>,
+}
+
+impl<
+        SeverityType: IsSeverity,
+        MessageType: HasSeverity<SeverityType> + HasText,
+        FormatterType: Format<SeverityType, MessageType>,
+    > MmapWriter<SeverityType, MessageType, FormatterType>
+{
+    /// Create a new memory-mapped writer.
+    ///
+    /// Any existing file at the path is truncated.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the log file
+    /// * `capacity` - The size of the log file in bytes, which must not be zero
+    /// * `formatter` - The formatter to use for messages
+    ///
+    /// # Returns
+    ///
+    /// Returns the writer, or an error if the file could not be created or mapped.
+    pub fn new<PathType: AsRef<Path>>(
+        path: PathType,
+        capacity: usize,
+        formatter: FormatterType,
+    ) -> Result<Self> {
+        let path = path.as_ref().to_owned();
+        let map = Self::map_file(&path, capacity)?;
+
+        Ok(Self {
+            path,
+            capacity,
+            formatter,
+            rollover_policy: MmapRolloverPolicy::default(),
+            map,
+            offset: 0,
+            severity_type_phantom: PhantomData,
+            message_type_phantom: PhantomData,
+        })
+    }
+
+    /// Sets what happens when the file is too full for the next message.
+    ///
+    /// By default this is [`MmapRolloverPolicy::Rotate`].
+    pub fn with_rollover_policy(mut self, rollover_policy: MmapRolloverPolicy) -> Self {
+        self.rollover_policy = rollover_policy;
+        self
+    }
+
+    /// Gets the path that full files are renamed to by [`MmapRolloverPolicy::Rotate`].
+    pub fn rotated_path(&self) -> PathBuf {
+        let mut file_name = self
+            .path
+            .file_name()
+            .map(ToOwned::to_owned)
+            .unwrap_or_default();
+
+        file_name.push(".1");
+
+        self.path.with_file_name(file_name)
+    }
+
+    /// Creates a zero-filled file of the given size and maps it into memory.
+    fn map_file(path: &Path, capacity: usize) -> Result<MmapMut> {
+        if capacity == 0 {
+            return Err(Error::WriterError(
+                "memory-mapped log file must not be empty".to_owned(),
+            ));
+        }
+
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+
+        file.set_len(capacity as u64)?;
+
+        Self::map(&file)
+    }
+
+    fn map(file: &File) -> Result<MmapMut> {
+        // SAFETY: The file was just created by this writer, which holds the only mapping of it. Other
+        // processes modifying the file while it is mapped could still cause undefined behavior, so
+        // log files should not be shared with other writers.
+        Ok(unsafe { MmapMut::map_mut(file)? })
+    }
+
+    /// Makes room for an entry that does not fit in the rest of the file.
+    fn roll_over(&mut self) -> Result<()> {
+        match self.rollover_policy {
+            MmapRolloverPolicy::Rotate => {
+                self.map.flush()?;
+                fs::rename(&self.path, self.rotated_path())?;
+
+                self.map = Self::map_file(&self.path, self.capacity)?;
+            }
+            MmapRolloverPolicy::Wrap => {
+                // Clear the unused end so that it does not hold parts of older messages
+                self.map[self.offset..].fill(0);
+            }
+            MmapRolloverPolicy::Error => {
+                return Err(Error::WriterError(
+                    "memory-mapped log file is full".to_owned(),
+                ));
+            }
+        }
+
+        self.offset = 0;
+
+        Ok(())
+    }
+}
+
+impl<
+        SeverityType: IsSeverity,
+        MessageType: HasSeverity<SeverityType> + HasText,
+        FormatterType: Format<SeverityType, MessageType>,
+    > Write<SeverityType, MessageType> for MmapWriter<SeverityType, MessageType, FormatterType>
+{
+    fn write(&mut self, message: &MessageType) -> Result<()> {
+        let mut buffer = Vec::new();
+
+        self.formatter.format_generic(message, &mut buffer)?;
+        end_line(&mut buffer);
+
+        self.write_line(&buffer)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        let mut buffer = Vec::new();
+
+        self.formatter.flush(&mut buffer)?;
+        end_line(&mut buffer);
+
+        self.write_line(&buffer)?;
+        self.map.flush()?;
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "mmap"
+    }
+}
+
+impl<
+        SeverityType: IsSeverity,
+        MessageType: HasSeverity<SeverityType> + HasText,
+        FormatterType: Format<SeverityType, MessageType>,
+    > MmapWriter<SeverityType, MessageType, FormatterType>
+{
+    /// Copies a formatted line into the mapping, rolling over first if it does not fit.
+    fn write_line(&mut self, buffer: &[u8]) -> Result<()> {
+        if buffer.is_empty() {
+            return Ok(());
+        }
+
+        if buffer.len() > self.capacity {
+            return Err(Error::WriterError(format!(
+                "log message of {} bytes does not fit in a memory-mapped log file of {} bytes",
+                buffer.len(),
+                self.capacity
+            )));
+        }
+
+        if self.offset + buffer.len() > self.capacity {
+            self.roll_over()?;
+        }
+
+        let end = self.offset + buffer.len();
+
+        // After wrapping, the message may only partly overwrite an older one, whose remainder is
+        // cleared so that it is not mistaken for a complete message
+        if !matches!(self.map[end - 1], b'\n' | 0) {
+            let remainder = self.map[end..]
+                .iter()
+                .position(|byte| *byte == b'\n')
+                .map_or(self.capacity, |index| end + index + 1);
+
+            self.map[end..remainder].fill(0);
+        }
+
+        self.map[self.offset..end].copy_from_slice(buffer);
+        self.offset = end;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{formatters::Plaintext, FromCoreFields, Message, Severity};
+    use std::{env, process};
+
+    fn temp_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!(
+            "got-ur-logs-uwu-mmap-{}-{}.log",
+            name,
+            process::id()
+        ))
+    }
+
+    fn write(
+        writer: &mut MmapWriter<Severity, Message<Severity>, Plaintext>,
+        text: &str,
+    ) -> Result<()> {
+        writer.write(&Message::from_core_fields(Severity::Info, text))
+    }
+
+    fn read(path: &Path) -> Result<String> {
+        Ok(String::from_utf8_lossy(&fs::read(path)?).replace('\0', ""))
+    }
+
+    #[test]
+    fn writes_sequentially() -> Result<()> {
+        let path = temp_path("sequential");
+        let mut writer = MmapWriter::new(&path, 64, Plaintext::new_default())?;
+
+        write(&mut writer, "first")?;
+        write(&mut writer, "second")?;
+        writer.flush()?;
+
+        assert_eq!(fs::metadata(&path)?.len(), 64);
+        assert_eq!(read(&path)?, "[info] first\n[info] second\n");
+
+        fs::remove_file(&path)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn rotates_when_full() -> Result<()> {
+        let path = temp_path("rotate");
+        let mut writer = MmapWriter::new(&path, 32, Plaintext::new_default())?;
+        let rotated_path = writer.rotated_path();
+
+        write(&mut writer, "first")?;
+        write(&mut writer, "second")?;
+        write(&mut writer, "third")?;
+        writer.flush()?;
+
+        assert_eq!(read(&rotated_path)?, "[info] first\n[info] second\n");
+        assert_eq!(read(&path)?, "[info] third\n");
+
+        fs::remove_file(&path)?;
+        fs::remove_file(&rotated_path)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn wraps_when_full() -> Result<()> {
+        let path = temp_path("wrap");
+        let mut writer = MmapWriter::new(&path, 32, Plaintext::new_default())?
+            .with_rollover_policy(MmapRolloverPolicy::Wrap);
+
+        write(&mut writer, "first")?;
+        write(&mut writer, "second")?;
+        write(&mut writer, "3")?;
+        writer.flush()?;
+
+        assert_eq!(read(&path)?, "[info] 3\n[info] second\n");
+        assert!(!writer.rotated_path().exists());
+
+        fs::remove_file(&path)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn errors_when_full() -> Result<()> {
+        let path = temp_path("error");
+        let mut writer = MmapWriter::new(&path, 32, Plaintext::new_default())?
+            .with_rollover_policy(MmapRolloverPolicy::Error);
+
+        write(&mut writer, "first")?;
+        write(&mut writer, "second")?;
+
+        assert!(write(&mut writer, "third").is_err());
+
+        fs::remove_file(&path)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn message_too_large() -> Result<()> {
+        let path = temp_path("too-large");
+        let mut writer = MmapWriter::new(&path, 8, Plaintext::new_default())?;
+
+        assert!(write(&mut writer, "this does not fit").is_err());
+
+        fs::remove_file(&path)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn empty_capacity() {
+        assert!(MmapWriter::<Severity, Message<Severity>, Plaintext>::new(
+            temp_path("empty"),
+            0,
+            Plaintext::new_default()
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    traits::{HasSeverity, HasText},
+    IsSeverity, Result, Write,
+};
+use std::{collections::VecDeque, marker::PhantomData};
+
+/// A writer that retains the most recent messages in memory.
+///
+/// Once the buffer is full, the oldest messages are evicted to make room for new ones. This is
+/// useful for including recent log history in crash reports.
+///
+/// # Example
+///
+/// ```
+/// # use std::sync::{Arc, Mutex};
+/// # use got_ur_logs_uwu::{writers::RingBufferWriter, HasText, Logger, Message, Severity};
+/// #
+/// let mut logger = Logger::<Severity, Message<Severity>>::default();
+///
+/// let writer = Arc::new(Mutex::new(RingBufferWriter::new(2)));
+///
+/// logger.add_writer_shared(writer.clone());
+///
+/// logger.log_info("first");
+/// logger.log_info("second");
+/// logger.log_info("third");
+///
+/// let snapshot = writer.lock().unwrap().snapshot();
+///
+/// assert_eq!(snapshot.len(), 2);
+/// assert_eq!(snapshot[0].text(), "second");
+/// assert_eq!(snapshot[1].text(), "third");
+/// ```
+pub struct RingBufferWriter<
+    SeverityType: IsSeverity,
+    MessageType: HasSeverity<SeverityType> + HasText + Clone,
+> {
+    messages: VecDeque<MessageType>,
+    capacity: usize,
+    severity_type_phantom: PhantomData<SeverityType>,
+}
+
+impl<SeverityType: IsSeverity, MessageType: HasSeverity<SeverityType> + HasText + Clone>
+    RingBufferWriter<SeverityType, MessageType>
+{
+    /// Create a new ring buffer writer that retains up to `capacity` messages.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            messages: VecDeque::with_capacity(capacity),
+            capacity,
+            severity_type_phantom: PhantomData,
+        }
+    }
+
+    /// Gets a copy of the retained messages, from oldest to newest.
+    pub fn snapshot(&self) -> Vec<MessageType> {
+        self.messages.iter().cloned().collect()
+    }
+}
+
+impl<SeverityType: IsSeverity, MessageType: HasSeverity<SeverityType> + HasText + Clone>
+    Write<SeverityType, MessageType> for RingBufferWriter<SeverityType, MessageType>
+{
+    fn write(&mut self, message: &MessageType) -> Result<()> {
+        if self.capacity == 0 {
+            return Ok(());
+        }
+
+        if self.messages.len() == self.capacity {
+            self.messages.pop_front();
+        }
+
+        self.messages.push_back(message.clone());
+
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "ring_buffer"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FromCoreFields, Message, Severity};
+
+    #[test]
+    fn evicts_oldest() -> Result<()> {
+        let mut writer = RingBufferWriter::new(2);
+
+        writer.write(&Message::from_core_fields(Severity::Info, "first"))?;
+        writer.write(&Message::from_core_fields(Severity::Info, "second"))?;
+
+        assert_eq!(writer.snapshot().len(), 2);
+
+        writer.write(&Message::from_core_fields(Severity::Info, "third"))?;
+
+        let snapshot = writer.snapshot();
+
+        assert_eq!(snapshot.len(), 2);
+        assert_eq!(snapshot[0].text(), "second");
+        assert_eq!(snapshot[1].text(), "third");
+
+        Ok(())
+    }
+
+    #[test]
+    fn zero_capacity() -> Result<()> {
+        let mut writer = RingBufferWriter::new(0);
+
+        writer.write(&Message::from_core_fields(Severity::Info, "first"))?;
+
+        assert!(writer.snapshot().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn name() {
+        assert_eq!(
+            Write::<Severity, Message<Severity>>::name(&RingBufferWriter::new(1)),
+            "ring_buffer"
+        );
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+//! Clearing writers affects every test that uses the global logger, so this is kept in its own
+//! test binary.
+
+use std::sync::{Arc, Mutex};
+
+use got_ur_logs_uwu::{writers::CaptureWriter, Logger, Message, Severity};
+
+#[test]
+fn clear_writers_global() {
+    let mut logger = Logger::<Severity, Message<Severity>>::global_mut();
+
+    let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+    logger.add_writer_shared(writer.clone());
+
+    assert_eq!(logger.writer_count(), 1);
+
+    logger.log_info("before");
+    logger.clear_writers();
+    logger.log_info("after");
+
+    drop(logger);
+
+    assert_eq!(
+        Logger::<Severity, Message<Severity>>::global().writer_count(),
+        0
+    );
+    assert_eq!(writer.lock().unwrap().messages().len(), 1);
+}
@@ -0,0 +1,53 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+//! Installing a global logger handle affects the whole process, so this is kept in its own test
+//! binary.
+
+use std::sync::{Arc, Mutex};
+
+use got_ur_logs_uwu::{
+    writers::CaptureWriter, GlobalLoggerHandle, HasSeverity, HasText, Logger, Message, Severity,
+};
+
+#[test]
+fn logs_to_installed_global_logger() {
+    GlobalLoggerHandle::install::<Severity, Message<Severity>>();
+
+    let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+    Logger::<Severity, Message<Severity>>::global_mut().add_writer_shared(writer.clone());
+
+    let logger = GlobalLoggerHandle::get().expect("a global logger was installed");
+
+    logger.log_warning("global handle warning");
+    logger.log_with_severity(Severity::Custom(210, "alert"), "global handle alert");
+
+    let messages = writer
+        .lock()
+        .unwrap()
+        .messages()
+        .into_iter()
+        .map(|message| (message.severity().clone(), message.text().to_owned()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        messages,
+        [
+            (Severity::Warning, "global handle warning".to_owned()),
+            (Severity::Error, "global handle alert".to_owned()),
+        ]
+    );
+}
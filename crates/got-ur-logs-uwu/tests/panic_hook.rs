@@ -0,0 +1,114 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+//! The panic hook is shared by the whole process, so setting it is kept in its own test binary.
+
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::{Arc, Mutex},
+};
+
+use got_ur_logs_uwu::{
+    writers::CaptureWriter, Error, HasSeverity, HasText, Logger, Message, Result, Severity, Write,
+};
+
+/// Held by each test while it replaces the panic hook.
+static PANIC_HOOK_LOCK: Mutex<()> = Mutex::new(());
+
+/// A writer that always fails.
+struct FailingWriter;
+
+impl Write<Severity, Message<Severity>> for FailingWriter {
+    fn write(&mut self, _message: &Message<Severity>) -> Result<()> {
+        Err(Error::WriterError("failed".to_owned()))
+    }
+
+    fn name(&self) -> &'static str {
+        "failing"
+    }
+}
+
+/// A writer that panics when it writes a message.
+struct PanickingWriter;
+
+impl Write<Severity, Message<Severity>> for PanickingWriter {
+    fn write(&mut self, _message: &Message<Severity>) -> Result<()> {
+        panic!("writer panicked");
+    }
+
+    fn name(&self) -> &'static str {
+        "panicking"
+    }
+}
+
+/// Runs a function with a logger set as the panic hook, restoring the previous hook afterwards.
+fn with_panic_hook(
+    logger: Logger<Severity, Message<Severity>>,
+    function: impl FnOnce(&'static Logger<Severity, Message<Severity>>),
+) {
+    let _lock = PANIC_HOOK_LOCK
+        .lock()
+        .unwrap_or_else(|error| error.into_inner());
+
+    let logger: &'static Logger<Severity, Message<Severity>> = Box::leak(Box::new(logger));
+
+    let previous_hook = panic::take_hook();
+
+    logger.set_as_panic_hook();
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| function(logger)));
+
+    panic::set_hook(previous_hook);
+
+    assert!(result.is_err(), "the function should have panicked");
+}
+
+#[test]
+fn logs_panics() {
+    let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+    let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+    logger.add_writer_shared(writer.clone());
+
+    // This would abort the tests if the hook did not ignore writer errors
+    logger.add_writer(FailingWriter);
+
+    with_panic_hook(logger, |_| panic!("something went wrong"));
+
+    let message = writer.lock().unwrap().last_message().unwrap();
+
+    assert_eq!(*message.severity(), Severity::Fatal);
+    assert!(message.text().contains("something went wrong"));
+}
+
+#[test]
+fn writer_panics() {
+    let mut logger = Logger::<Severity, Message<Severity>>::default();
+
+    logger.add_writer(PanickingWriter);
+
+    let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+    logger.add_writer_shared(writer.clone());
+
+    // This would deadlock if the hook waited for the panicking writer to be unlocked
+    with_panic_hook(logger, |logger| logger.log_info("hello"));
+
+    let message = writer.lock().unwrap().last_message().unwrap();
+
+    assert_eq!(*message.severity(), Severity::Fatal);
+    assert!(message.text().contains("writer panicked"));
+}
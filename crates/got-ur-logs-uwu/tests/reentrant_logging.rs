@@ -0,0 +1,56 @@
+// Copyright (c) 2023 Sophie Katz
+//
+// This file is part of got-ur-logs-uwu.
+//
+// got-ur-logs-uwu is free software: you can redistribute it and/or modify it under the terms of the
+// GNU General Public License as published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// got-ur-logs-uwu is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
+// not, see <https://www.gnu.org/licenses/>.
+
+//! Format arguments that use the global logger need it to be unlocked, so this is kept in its own
+//! test binary where nothing else holds it.
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use got_ur_logs_uwu::{log_info, writers::CaptureWriter, HasText, Logger, Message, Severity};
+
+/// Logs and configures the global logger while it is being formatted.
+struct UsesGlobalLogger;
+
+impl fmt::Display for UsesGlobalLogger {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        log_info!("formatting");
+
+        Logger::<Severity, Message<Severity>>::global_mut().set_min_severity(Severity::Trace);
+
+        write!(formatter, "value")
+    }
+}
+
+#[test]
+fn format_arguments_use_global_logger() {
+    let writer = Arc::new(Mutex::new(CaptureWriter::new()));
+
+    Logger::<Severity, Message<Severity>>::global_mut().add_writer_shared(writer.clone());
+
+    log_info!("formatted {}", UsesGlobalLogger);
+
+    let texts = writer
+        .lock()
+        .unwrap()
+        .messages()
+        .iter()
+        .map(|message| message.text().to_owned())
+        .collect::<Vec<_>>();
+
+    assert_eq!(texts, ["formatting", "formatted value"]);
+}
@@ -13,6 +13,8 @@
 // You should have received a copy of the GNU General Public License along with got-ur-logs-uwu. If
 // not, see <https://www.gnu.org/licenses/>.
 
+use std::sync::RwLockWriteGuard;
+
 use got_ur_logs_uwu::{formatters::Plaintext, writers::ConsoleWriter, Logger, Message, Severity};
 use rstest::rstest;
 
@@ -24,11 +26,16 @@ enum LoggerType {
 #[derive(Default)]
 struct TestContext {
     logger: Option<Logger<Severity, Message<Severity>>>,
+    global_logger: Option<RwLockWriteGuard<'static, Logger<Severity, Message<Severity>>>>,
 }
 
 impl TestContext {
     fn get_default_logger_global(&mut self) -> &mut Logger<Severity, Message<Severity>> {
-        got_ur_logs_uwu::Logger::global()
+        if self.global_logger.is_none() {
+            self.global_logger = Some(got_ur_logs_uwu::Logger::global_mut());
+        }
+
+        self.global_logger.as_mut().unwrap()
     }
 
     fn get_default_logger_local(&mut self) -> &mut Logger<Severity, Message<Severity>> {